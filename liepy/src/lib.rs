@@ -1,24 +1,258 @@
 use ndarray as nd;
 
+use std::collections::HashMap;
+
 use num_complex::Complex64;
-use numpy::{PyArray2, PyReadonlyArray2, ToPyArray};
-use pyo3::prelude::{pymodule, PyModule, PyResult, Python};
+use numpy::{PyArray1, PyArray2, PyReadonlyArray1, PyReadonlyArray2, ToPyArray};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::{pyclass, pymethods, pymodule, PyModule, PyResult, Python};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 
 #[allow(unused_imports)]
 use lie::lie_algebra::{
-    cross, dot, find_d_coefficients, find_structure_constants, su_anticommutator, su_commutator,
+    cross, dot, find_d_coefficients, find_d_tensor, find_structure_constants, su_anticommutator,
+    su_commutator, DTensor, StructureConstants,
+};
+use lie::bch::{bch, zassenhaus, Expansion};
+use lie::euler::{
+    su2_euler_measure, su2_from_euler, su2_to_euler, su_euler_measure, su_euler_ranges,
+    su_from_euler, su_to_euler, EulerConvention,
 };
+use lie::exponential::{all_logarithms, exp_map, log_map};
+use lie::lie_algebra::LieAlgebra as Algebra;
 #[allow(unused_imports)]
 use lie::spherical::hermitian_basis_from_spin;
 
+use lie::classical::{gen_gl, gen_sl, gen_so, gen_sp, gen_u, Normalization};
 use lie::gellmann::gen_gellmann;
+use lie::random::{
+    haar_orthogonal, haar_special_orthogonal, haar_special_unitary, haar_symplectic,
+    haar_unitary, random_element,
+};
+use lie::real_forms::{gen_lorentz, gen_so21, gen_so_pq, gen_su11, gen_su_pq};
+use lie::rotation::{lift_path, Quaternion};
 use lie::sylvester::gen_sylvester;
+use lie::weingarten::{orthogonal_moment, twirl, unitary_moment, weingarten_unitary};
+use lie::wigner::{wigner_d, wigner_small_d};
 
 use lie::su2::gen_sl2;
 use lie::su2::gen_su2;
 
+/// Structure constants are exchanged with Python as a dict {(a, b, c): f_abc}
+type PyTensor = HashMap<(usize, usize, usize), Complex64>;
+
+fn to_py_tensor(x: &StructureConstants) -> PyTensor {
+    x.iter().map(|(a, b, c, f)| ((a, b, c), f)).collect()
+}
+
+fn to_normalization(normalization: Option<f64>) -> Normalization {
+    normalization.map_or(Normalization::Unit, Normalization::Trace)
+}
+
+fn from_py_tensor(dim: usize, x: PyTensor) -> StructureConstants {
+    StructureConstants::from_entries(dim, x.into_iter().map(|((a, b, c), f)| (a, b, c, f)))
+}
+
+/// A Lie algebra given by a matrix basis, with the structure constants, d coefficients and
+/// trace metric computed once
+#[pyclass]
+struct LieAlgebra {
+    inner: Algebra,
+}
+
+#[pymethods]
+impl LieAlgebra {
+    #[new]
+    fn new(basis: Vec<PyReadonlyArray2<Complex64>>) -> Self {
+        let basis: Vec<nd::Array2<Complex64>> =
+            basis.iter().map(|x| x.as_array().to_owned()).collect();
+
+        LieAlgebra {
+            inner: Algebra::new(basis),
+        }
+    }
+
+    /// The simple Lie algebra of a Cartan type such as "A3", "G2" or "E8", in its Chevalley
+    /// basis with exact integer structure constants
+    #[staticmethod]
+    fn from_cartan_type(cartan_type: &str) -> PyResult<Self> {
+        let inner = Algebra::from_cartan_type(cartan_type).map_err(PyValueError::new_err)?;
+        Ok(LieAlgebra { inner })
+    }
+
+    /// Number of basis elements
+    #[getter]
+    fn dim(&self) -> usize {
+        self.inner.dim()
+    }
+
+    fn basis<'py>(&self, py: Python<'py>) -> Vec<&'py PyArray2<Complex64>> {
+        self.inner.basis().iter().map(|x| x.to_pyarray(py)).collect()
+    }
+
+    fn structure_constants(&self) -> PyTensor {
+        to_py_tensor(self.inner.structure_constants())
+    }
+
+    fn d_tensor<'py>(&self, py: Python<'py>) -> (PyTensor, &'py PyArray2<Complex64>) {
+        let d_tensor = self.inner.d_tensor();
+        (to_py_tensor(&d_tensor.d), d_tensor.identity.to_pyarray(py))
+    }
+
+    /// The trace metric Tr(T_a T_b)
+    fn metric<'py>(&self, py: Python<'py>) -> &'py PyArray2<Complex64> {
+        self.inner.metric().to_pyarray(py)
+    }
+
+    /// Coordinates of the commutator [x, y]
+    fn bracket<'py>(
+        &self,
+        py: Python<'py>,
+        x: PyReadonlyArray1<Complex64>,
+        y: PyReadonlyArray1<Complex64>,
+    ) -> &'py PyArray1<Complex64> {
+        let res = self
+            .inner
+            .bracket(&x.as_array().to_owned(), &y.as_array().to_owned());
+
+        res.to_pyarray(py)
+    }
+
+    /// The anticommutator {x, y} as the coefficient of the identity and the coordinates of the
+    /// remaining part
+    fn anticommutator<'py>(
+        &self,
+        py: Python<'py>,
+        x: PyReadonlyArray1<Complex64>,
+        y: PyReadonlyArray1<Complex64>,
+    ) -> (Complex64, &'py PyArray1<Complex64>) {
+        let (c, res) = self
+            .inner
+            .anticommutator(&x.as_array().to_owned(), &y.as_array().to_owned());
+
+        (c, res.to_pyarray(py))
+    }
+
+    /// Coordinates of a matrix in the basis
+    fn to_coordinates<'py>(
+        &self,
+        py: Python<'py>,
+        m: PyReadonlyArray2<Complex64>,
+    ) -> &'py PyArray1<Complex64> {
+        self.inner
+            .to_coordinates(&m.as_array().to_owned())
+            .to_pyarray(py)
+    }
+
+    /// The matrix with the given coordinates
+    #[name = "from_coordinates"]
+    fn coordinates_to_matrix<'py>(
+        &self,
+        py: Python<'py>,
+        x: PyReadonlyArray1<Complex64>,
+    ) -> &'py PyArray2<Complex64> {
+        self.inner
+            .from_coordinates(&x.as_array().to_owned())
+            .to_pyarray(py)
+    }
+
+    /// The group element exp(sum_a x_a T_a)
+    fn exp_map<'py>(
+        &self,
+        py: Python<'py>,
+        x: PyReadonlyArray1<Complex64>,
+    ) -> &'py PyArray2<Complex64> {
+        exp_map(&self.inner, &x.as_array().to_owned()).to_pyarray(py)
+    }
+
+    /// The principal logarithm of a unitary matrix, with whether it is on the branch cut
+    fn log_map<'py>(
+        &self,
+        py: Python<'py>,
+        u: PyReadonlyArray2<Complex64>,
+    ) -> PyResult<(&'py PyArray1<Complex64>, bool)> {
+        let log = log_map(&self.inner, &u.as_array().to_owned()).map_err(PyValueError::new_err)?;
+        Ok((log.coords.to_pyarray(py), log.branch_cut))
+    }
+
+    /// A random element with standard Gaussian coordinates, reproducible from the seed
+    fn random_element<'py>(&self, py: Python<'py>, seed: u64) -> &'py PyArray1<Complex64> {
+        random_element(&self.inner, &mut StdRng::seed_from_u64(seed)).to_pyarray(py)
+    }
+
+    /// The Baker-Campbell-Hausdorff terms of log(e^x e^y) up to the given order, with the
+    /// error estimate of each truncation
+    fn bch<'py>(
+        &self,
+        py: Python<'py>,
+        x: PyReadonlyArray1<Complex64>,
+        y: PyReadonlyArray1<Complex64>,
+        order: usize,
+    ) -> (Vec<&'py PyArray1<Complex64>>, Vec<f64>) {
+        let x = x.as_array().to_owned();
+        let y = y.as_array().to_owned();
+        expansion_to_py(py, bch(&x, &y, order, self.inner.structure_constants()))
+    }
+
+    /// The Zassenhaus factors [C_2, ..., C_order] of e^(x + y) = e^x e^y e^C_2 ..., with the
+    /// error estimate of each truncation
+    fn zassenhaus<'py>(
+        &self,
+        py: Python<'py>,
+        x: PyReadonlyArray1<Complex64>,
+        y: PyReadonlyArray1<Complex64>,
+        order: usize,
+    ) -> (Vec<&'py PyArray1<Complex64>>, Vec<f64>) {
+        let x = x.as_array().to_owned();
+        let y = y.as_array().to_owned();
+        expansion_to_py(py, zassenhaus(&x, &y, order, self.inner.structure_constants()))
+    }
+
+    /// Every logarithm of smallest norm of a unitary matrix, up to limit of them
+    fn all_logarithms<'py>(
+        &self,
+        py: Python<'py>,
+        u: PyReadonlyArray2<Complex64>,
+        limit: usize,
+    ) -> PyResult<Vec<&'py PyArray1<Complex64>>> {
+        if limit == 0 {
+            return Err(PyValueError::new_err("At least one logarithm must be requested"));
+        }
+        let logarithms = all_logarithms(&self.inner, &u.as_array().to_owned(), limit)
+            .map_err(PyValueError::new_err)?;
+        Ok(logarithms.iter().map(|x| x.to_pyarray(py)).collect())
+    }
+}
+
+fn expansion_to_py(
+    py: Python<'_>,
+    expansion: Expansion,
+) -> (Vec<&PyArray1<Complex64>>, Vec<f64>) {
+    let terms = expansion.terms.iter().map(|x| x.to_pyarray(py)).collect();
+    (terms, expansion.errors)
+}
+
+fn to_tuple(q: Quaternion) -> (f64, f64, f64, f64) {
+    (q.w, q.x, q.y, q.z)
+}
+
+fn from_tuple(q: (f64, f64, f64, f64)) -> Quaternion {
+    Quaternion::new(q.0, q.1, q.2, q.3)
+}
+
+fn parse_convention(convention: &str) -> PyResult<EulerConvention> {
+    match convention {
+        "zyz" => Ok(EulerConvention::Zyz),
+        "zxz" => Ok(EulerConvention::Zxz),
+        _ => Err(PyValueError::new_err("The convention is either zyz or zxz")),
+    }
+}
+
 #[pymodule]
 fn liepy(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add_class::<LieAlgebra>()?;
+
     /// Generate matrix representation of su(d) via clock and shift
     #[pyfn(m, "gen_sylvester")]
     fn gen_sylvester_py<'py>(py: Python<'py>, d: usize) -> Vec<&'py PyArray2<Complex64>> {
@@ -37,6 +271,330 @@ fn liepy(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
         basis
     }
 
+    /// Generate a basis of gl(n). With `normalization = c`, Tr(T_a^dagger T_a) = c
+    #[pyfn(m, "gen_gl")]
+    fn gen_gl_py<'py>(
+        py: Python<'py>,
+        n: usize,
+        normalization: Option<f64>,
+    ) -> Vec<&'py PyArray2<Complex64>> {
+        let basis = gen_gl(n, to_normalization(normalization));
+        basis.iter().map(|x| x.to_pyarray(py)).collect()
+    }
+
+    /// Generate a basis of sl(n). With `normalization = c`, Tr(T_a^dagger T_a) = c
+    #[pyfn(m, "gen_sl")]
+    fn gen_sl_n_py<'py>(
+        py: Python<'py>,
+        n: usize,
+        normalization: Option<f64>,
+    ) -> Vec<&'py PyArray2<Complex64>> {
+        let basis = gen_sl(n, to_normalization(normalization));
+        basis.iter().map(|x| x.to_pyarray(py)).collect()
+    }
+
+    /// Generate a basis of so(n). With `normalization = c`, Tr(T_a^dagger T_a) = c
+    #[pyfn(m, "gen_so")]
+    fn gen_so_py<'py>(
+        py: Python<'py>,
+        n: usize,
+        normalization: Option<f64>,
+    ) -> Vec<&'py PyArray2<Complex64>> {
+        let basis = gen_so(n, to_normalization(normalization));
+        basis.iter().map(|x| x.to_pyarray(py)).collect()
+    }
+
+    /// Generate a basis of sp(2n). With `normalization = c`, Tr(T_a^dagger T_a) = c
+    #[pyfn(m, "gen_sp")]
+    fn gen_sp_py<'py>(
+        py: Python<'py>,
+        n: usize,
+        normalization: Option<f64>,
+    ) -> Vec<&'py PyArray2<Complex64>> {
+        let basis = gen_sp(n, to_normalization(normalization));
+        basis.iter().map(|x| x.to_pyarray(py)).collect()
+    }
+
+    /// Generate a basis of u(n). With `normalization = c`, Tr(T_a^dagger T_a) = c
+    #[pyfn(m, "gen_u")]
+    fn gen_u_py<'py>(
+        py: Python<'py>,
+        n: usize,
+        normalization: Option<f64>,
+    ) -> Vec<&'py PyArray2<Complex64>> {
+        let basis = gen_u(n, to_normalization(normalization));
+        basis.iter().map(|x| x.to_pyarray(py)).collect()
+    }
+
+    /// Generate a basis of so(p, q), with rotations and boosts
+    #[pyfn(m, "gen_so_pq")]
+    fn gen_so_pq_py<'py>(py: Python<'py>, p: usize, q: usize) -> Vec<&'py PyArray2<Complex64>> {
+        gen_so_pq(p, q).iter().map(|x| x.to_pyarray(py)).collect()
+    }
+
+    /// Generate a basis of su(p, q)
+    #[pyfn(m, "gen_su_pq")]
+    fn gen_su_pq_py<'py>(py: Python<'py>, p: usize, q: usize) -> Vec<&'py PyArray2<Complex64>> {
+        gen_su_pq(p, q).iter().map(|x| x.to_pyarray(py)).collect()
+    }
+
+    /// Generate the Lorentz algebra so(3, 1), as the rotations J_x, J_y, J_z and the boosts
+    /// K_x, K_y, K_z
+    #[pyfn(m, "gen_lorentz")]
+    fn gen_lorentz_py<'py>(py: Python<'py>) -> Vec<&'py PyArray2<Complex64>> {
+        gen_lorentz().iter().map(|x| x.to_pyarray(py)).collect()
+    }
+
+    /// Generate so(2, 1) acting on (x, y, t), as the rotation L and the boosts K_x, K_y
+    #[pyfn(m, "gen_so21")]
+    fn gen_so21_py<'py>(py: Python<'py>) -> Vec<&'py PyArray2<Complex64>> {
+        gen_so21().iter().map(|x| x.to_pyarray(py)).collect()
+    }
+
+    /// Generate su(1, 1), with the same commutators as gen_so21
+    #[pyfn(m, "gen_su11")]
+    fn gen_su11_py<'py>(py: Python<'py>) -> Vec<&'py PyArray2<Complex64>> {
+        gen_su11().iter().map(|x| x.to_pyarray(py)).collect()
+    }
+
+    /// Sample U(n) with the Haar measure, reproducible from the seed
+    #[pyfn(m, "haar_unitary")]
+    fn haar_unitary_py<'py>(py: Python<'py>, n: usize, seed: u64) -> &'py PyArray2<Complex64> {
+        haar_unitary(n, &mut StdRng::seed_from_u64(seed)).to_pyarray(py)
+    }
+
+    /// Sample SU(n) with the Haar measure, reproducible from the seed
+    #[pyfn(m, "haar_special_unitary")]
+    fn haar_special_unitary_py<'py>(
+        py: Python<'py>,
+        n: usize,
+        seed: u64,
+    ) -> &'py PyArray2<Complex64> {
+        haar_special_unitary(n, &mut StdRng::seed_from_u64(seed)).to_pyarray(py)
+    }
+
+    /// Sample O(n) with the Haar measure, reproducible from the seed
+    #[pyfn(m, "haar_orthogonal")]
+    fn haar_orthogonal_py<'py>(py: Python<'py>, n: usize, seed: u64) -> &'py PyArray2<f64> {
+        haar_orthogonal(n, &mut StdRng::seed_from_u64(seed)).to_pyarray(py)
+    }
+
+    /// Sample SO(n) with the Haar measure, reproducible from the seed
+    #[pyfn(m, "haar_special_orthogonal")]
+    fn haar_special_orthogonal_py<'py>(
+        py: Python<'py>,
+        n: usize,
+        seed: u64,
+    ) -> &'py PyArray2<f64> {
+        haar_special_orthogonal(n, &mut StdRng::seed_from_u64(seed)).to_pyarray(py)
+    }
+
+    /// Sample the compact symplectic group Sp(n) in U(2n) with the Haar measure, reproducible
+    /// from the seed
+    #[pyfn(m, "haar_symplectic")]
+    fn haar_symplectic_py<'py>(py: Python<'py>, n: usize, seed: u64) -> &'py PyArray2<Complex64> {
+        haar_symplectic(n, &mut StdRng::seed_from_u64(seed)).to_pyarray(py)
+    }
+
+    /// The unitary Weingarten function Wg(sigma, d), as a string for fractions.Fraction
+    #[pyfn(m, "weingarten_unitary")]
+    fn weingarten_unitary_py(sigma: Vec<usize>, d: usize) -> String {
+        weingarten_unitary(&sigma, d).to_string()
+    }
+
+    /// The Haar average over U(d) of U_{i_1 j_1} ... conj(U_{i'_1 j'_1}) ..., as a string for
+    /// fractions.Fraction
+    #[pyfn(m, "unitary_moment")]
+    fn unitary_moment_py(
+        i: Vec<usize>,
+        j: Vec<usize>,
+        i_conj: Vec<usize>,
+        j_conj: Vec<usize>,
+        d: usize,
+    ) -> String {
+        unitary_moment(&i, &j, &i_conj, &j_conj, d).to_string()
+    }
+
+    /// The Haar average over O(d) of O_{i_1 j_1} ..., as a string for fractions.Fraction
+    #[pyfn(m, "orthogonal_moment")]
+    fn orthogonal_moment_py(i: Vec<usize>, j: Vec<usize>, d: usize) -> String {
+        orthogonal_moment(&i, &j, d).to_string()
+    }
+
+    /// The twirl of an operator on k copies of C^d over U(d)
+    #[pyfn(m, "twirl")]
+    fn twirl_py<'py>(
+        py: Python<'py>,
+        x: PyReadonlyArray2<Complex64>,
+        d: usize,
+        k: usize,
+    ) -> &'py PyArray2<Complex64> {
+        twirl(&x.as_array().to_owned(), d, k).to_pyarray(py)
+    }
+
+    /// The element of SU(2) with Euler angles in the convention "zyz" or "zxz"
+    #[pyfn(m, "su2_from_euler")]
+    fn su2_from_euler_py<'py>(
+        py: Python<'py>,
+        alpha: f64,
+        beta: f64,
+        gamma: f64,
+        convention: &str,
+    ) -> PyResult<&'py PyArray2<Complex64>> {
+        let convention = parse_convention(convention)?;
+        Ok(su2_from_euler(alpha, beta, gamma, convention).to_pyarray(py))
+    }
+
+    /// The Euler angles (alpha, beta, gamma, gimbal_lock) of an element of SU(2) in the
+    /// convention "zyz" or "zxz"
+    #[pyfn(m, "su2_to_euler")]
+    fn su2_to_euler_py(
+        u: PyReadonlyArray2<Complex64>,
+        convention: &str,
+    ) -> PyResult<(f64, f64, f64, bool)> {
+        let convention = parse_convention(convention)?;
+        let angles = su2_to_euler(&u.as_array().to_owned(), convention);
+        Ok((angles.alpha, angles.beta, angles.gamma, angles.gimbal_lock))
+    }
+
+    /// The normalized Haar measure of SU(2) in Euler angles
+    #[pyfn(m, "su2_euler_measure")]
+    fn su2_euler_measure_py(beta: f64) -> f64 {
+        su2_euler_measure(beta)
+    }
+
+    /// The element of SU(n) with the given generalized Euler angles
+    #[pyfn(m, "su_from_euler")]
+    fn su_from_euler_py<'py>(
+        py: Python<'py>,
+        n: usize,
+        angles: Vec<f64>,
+    ) -> &'py PyArray2<Complex64> {
+        su_from_euler(n, &angles).to_pyarray(py)
+    }
+
+    /// The generalized Euler angles of an element of SU(n)
+    #[pyfn(m, "su_to_euler")]
+    fn su_to_euler_py(u: PyReadonlyArray2<Complex64>) -> Vec<f64> {
+        su_to_euler(&u.as_array().to_owned())
+    }
+
+    /// The upper limits of the ranges of the generalized Euler angles of SU(n)
+    #[pyfn(m, "su_euler_ranges")]
+    fn su_euler_ranges_py(n: usize) -> Vec<f64> {
+        su_euler_ranges(n)
+    }
+
+    /// The normalized Haar measure of SU(n) in generalized Euler angles
+    #[pyfn(m, "su_euler_measure")]
+    fn su_euler_measure_py(n: usize, angles: Vec<f64>) -> f64 {
+        su_euler_measure(n, &angles)
+    }
+
+    /// The quaternion (w, x, y, z) of an element of SU(2)
+    #[pyfn(m, "quaternion_from_su2")]
+    fn quaternion_from_su2_py(u: PyReadonlyArray2<Complex64>) -> (f64, f64, f64, f64) {
+        to_tuple(Quaternion::from_su2(&u.as_array().to_owned()))
+    }
+
+    /// The element of SU(2) of the quaternion (w, x, y, z)
+    #[pyfn(m, "quaternion_to_su2")]
+    fn quaternion_to_su2_py<'py>(
+        py: Python<'py>,
+        q: (f64, f64, f64, f64),
+    ) -> &'py PyArray2<Complex64> {
+        from_tuple(q).to_su2().to_pyarray(py)
+    }
+
+    /// The rotation matrix of SO(3) of the quaternion (w, x, y, z)
+    #[pyfn(m, "quaternion_to_rotation_matrix")]
+    fn quaternion_to_rotation_matrix_py<'py>(
+        py: Python<'py>,
+        q: (f64, f64, f64, f64),
+    ) -> &'py PyArray2<f64> {
+        from_tuple(q).to_rotation_matrix().to_pyarray(py)
+    }
+
+    /// Lift a rotation matrix of SO(3) to the quaternion closest to the reference, or to the
+    /// one with w >= 0 if the reference is None
+    #[pyfn(m, "quaternion_from_rotation_matrix")]
+    fn quaternion_from_rotation_matrix_py(
+        r: PyReadonlyArray2<f64>,
+        reference: Option<(f64, f64, f64, f64)>,
+    ) -> (f64, f64, f64, f64) {
+        let reference = reference.map(from_tuple);
+        to_tuple(Quaternion::from_rotation_matrix(
+            &r.as_array().to_owned(),
+            reference.as_ref(),
+        ))
+    }
+
+    /// Lift a path of rotation matrices of SO(3) to continuous quaternions
+    #[pyfn(m, "lift_path")]
+    fn lift_path_py(rotations: Vec<PyReadonlyArray2<f64>>) -> Vec<(f64, f64, f64, f64)> {
+        let rotations: Vec<nd::Array2<f64>> =
+            rotations.iter().map(|r| r.as_array().to_owned()).collect();
+        lift_path(&rotations).into_iter().map(to_tuple).collect()
+    }
+
+    /// The quaternion (w, x, y, z) of the rotation by the angle around the axis
+    #[pyfn(m, "quaternion_from_axis_angle")]
+    fn quaternion_from_axis_angle_py(
+        axis: Vec<f64>,
+        angle: f64,
+    ) -> PyResult<(f64, f64, f64, f64)> {
+        if axis.len() != 3 {
+            return Err(PyValueError::new_err("The axis has three components"));
+        }
+        let axis = [axis[0], axis[1], axis[2]];
+        Ok(to_tuple(Quaternion::from_axis_angle(axis, angle)))
+    }
+
+    /// The unit axis and the angle in [0, 2 pi] of the quaternion (w, x, y, z)
+    #[pyfn(m, "quaternion_to_axis_angle")]
+    fn quaternion_to_axis_angle_py(q: (f64, f64, f64, f64)) -> (Vec<f64>, f64) {
+        let (axis, angle) = from_tuple(q).to_axis_angle();
+        (axis.to_vec(), angle)
+    }
+
+    /// The composition of the rotations q1 q2, applying q2 first
+    #[pyfn(m, "quaternion_multiply")]
+    fn quaternion_multiply_py(
+        q1: (f64, f64, f64, f64),
+        q2: (f64, f64, f64, f64),
+    ) -> (f64, f64, f64, f64) {
+        to_tuple(from_tuple(q1) * from_tuple(q2))
+    }
+
+    /// The spherical linear interpolation between the quaternions q1 and q2
+    #[pyfn(m, "slerp")]
+    fn slerp_py(
+        q1: (f64, f64, f64, f64),
+        q2: (f64, f64, f64, f64),
+        t: f64,
+    ) -> (f64, f64, f64, f64) {
+        to_tuple(from_tuple(q1).slerp(&from_tuple(q2), t))
+    }
+
+    /// The Wigner small-d matrix of spin j, ordered from m = j down to m = -j
+    #[pyfn(m, "wigner_small_d")]
+    fn wigner_small_d_py<'py>(py: Python<'py>, j: f64, beta: f64) -> &'py PyArray2<f64> {
+        wigner_small_d(j, beta).to_pyarray(py)
+    }
+
+    /// The Wigner D-matrix of spin j for the Euler angles in the convention "zyz", ordered from
+    /// m = j down to m = -j
+    #[pyfn(m, "wigner_d")]
+    fn wigner_d_py<'py>(
+        py: Python<'py>,
+        j: f64,
+        alpha: f64,
+        beta: f64,
+        gamma: f64,
+    ) -> &'py PyArray2<Complex64> {
+        wigner_d(j, alpha, beta, gamma).to_pyarray(py)
+    }
+
     /// Generate matrix representation of su(2) for spin j
     #[pyfn(m, "gen_su2")]
     fn gen_su_py<'py>(py: Python<'py>, j: f64) -> Vec<&'py PyArray2<Complex64>> {
@@ -59,13 +617,13 @@ fn liepy(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
     fn get_structure_constants_py<'py>(
         _py: Python<'py>,
         basis: Vec<PyReadonlyArray2<'py, Complex64>>,
-    ) -> std::collections::HashMap<(usize, usize), (usize, Complex64)> {
+    ) -> PyTensor {
         let basis: Vec<nd::Array2<Complex64>> =
             basis.iter().map(|x| x.as_array().to_owned()).collect();
 
         let struct_consts = find_structure_constants(basis.as_slice());
 
-        struct_consts
+        to_py_tensor(&struct_consts)
     }
 
     /// Find the anti-commutation coefficients for matrices in su(d)
@@ -73,26 +631,42 @@ fn liepy(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
     fn get_d_coefficients_py<'py>(
         _py: Python<'py>,
         basis: Vec<PyReadonlyArray2<'py, Complex64>>,
-    ) -> std::collections::HashMap<(usize, usize), (usize, Complex64)> {
+    ) -> PyTensor {
         let basis: Vec<nd::Array2<Complex64>> =
             basis.iter().map(|x| x.as_array().to_owned()).collect();
 
         let struct_consts = find_d_coefficients(basis.as_slice());
 
-        struct_consts
+        to_py_tensor(&struct_consts)
+    }
+
+    /// Find the anti-commutation coefficients for matrices in su(d), together with the
+    /// coefficients of the identity
+    #[pyfn(m, "get_d_tensor")]
+    fn get_d_tensor_py<'py>(
+        py: Python<'py>,
+        basis: Vec<PyReadonlyArray2<'py, Complex64>>,
+    ) -> (PyTensor, &'py PyArray2<Complex64>) {
+        let basis: Vec<nd::Array2<Complex64>> =
+            basis.iter().map(|x| x.as_array().to_owned()).collect();
+
+        let d_tensor = find_d_tensor(basis.as_slice());
+
+        (to_py_tensor(&d_tensor.d), d_tensor.identity.to_pyarray(py))
     }
 
     /// Find the commutation result of two matrices, given the structure constants
     #[pyfn(m, "su_commutator")]
     fn su_commutator_py<'py>(
         py: Python<'py>,
-        l_a: PyReadonlyArray2<'py, Complex64>,
-        l_b: PyReadonlyArray2<'py, Complex64>,
-        f_ijk: std::collections::HashMap<(usize, usize), (usize, Complex64)>,
+        l_a: PyReadonlyArray1<'py, Complex64>,
+        l_b: PyReadonlyArray1<'py, Complex64>,
+        f_ijk: PyTensor,
         basis: Vec<PyReadonlyArray2<'py, Complex64>>,
     ) -> &'py PyArray2<Complex64> {
         let basis: Vec<nd::Array2<Complex64>> =
             basis.iter().map(|x| x.as_array().to_owned()).collect();
+        let f_ijk = from_py_tensor(basis.len(), f_ijk);
         let res = su_commutator(
             &l_a.as_array().to_owned(),
             &l_b.as_array().to_owned(),
@@ -107,13 +681,18 @@ fn liepy(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
     #[pyfn(m, "su_anticommutator")]
     fn su_anticommutator_py<'py>(
         py: Python<'py>,
-        l_a: PyReadonlyArray2<'py, Complex64>,
-        l_b: PyReadonlyArray2<'py, Complex64>,
-        d_ijk: std::collections::HashMap<(usize, usize), (usize, Complex64)>,
+        l_a: PyReadonlyArray1<'py, Complex64>,
+        l_b: PyReadonlyArray1<'py, Complex64>,
+        d_ijk: PyTensor,
+        identity: PyReadonlyArray2<'py, Complex64>,
         basis: Vec<PyReadonlyArray2<'py, Complex64>>,
     ) -> &'py PyArray2<Complex64> {
         let basis: Vec<nd::Array2<Complex64>> =
             basis.iter().map(|x| x.as_array().to_owned()).collect();
+        let d_ijk = DTensor {
+            d: from_py_tensor(basis.len(), d_ijk),
+            identity: identity.as_array().to_owned(),
+        };
         let res = su_anticommutator(
             &l_a.as_array().to_owned(),
             &l_b.as_array().to_owned(),
@@ -128,15 +707,13 @@ fn liepy(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
     #[pyfn(m, "cross")]
     fn cross_py<'py>(
         py: Python<'py>,
-        l_a: PyReadonlyArray2<'py, Complex64>,
-        l_b: PyReadonlyArray2<'py, Complex64>,
-        f_ijk: std::collections::HashMap<(usize, usize), (usize, Complex64)>,
-    ) -> &'py PyArray2<Complex64> {
-        let res = cross(
-            &l_a.as_array().to_owned(),
-            &l_b.as_array().to_owned(),
-            &f_ijk,
-        );
+        l_a: PyReadonlyArray1<'py, Complex64>,
+        l_b: PyReadonlyArray1<'py, Complex64>,
+        f_ijk: PyTensor,
+    ) -> &'py PyArray1<Complex64> {
+        let l_a = l_a.as_array().to_owned();
+        let f_ijk = from_py_tensor(l_a.len(), f_ijk);
+        let res = cross(&l_a, &l_b.as_array().to_owned(), &f_ijk);
 
         res.to_pyarray(py)
     }
@@ -145,15 +722,13 @@ fn liepy(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
     #[pyfn(m, "dot")]
     fn dot_py<'py>(
         py: Python<'py>,
-        l_a: PyReadonlyArray2<'py, Complex64>,
-        l_b: PyReadonlyArray2<'py, Complex64>,
-        f_ijk: std::collections::HashMap<(usize, usize), (usize, Complex64)>,
-    ) -> &'py PyArray2<Complex64> {
-        let res = dot(
-            &l_a.as_array().to_owned(),
-            &l_b.as_array().to_owned(),
-            &f_ijk,
-        );
+        l_a: PyReadonlyArray1<'py, Complex64>,
+        l_b: PyReadonlyArray1<'py, Complex64>,
+        d_ijk: PyTensor,
+    ) -> &'py PyArray1<Complex64> {
+        let l_a = l_a.as_array().to_owned();
+        let d_ijk = from_py_tensor(l_a.len(), d_ijk);
+        let res = dot(&l_a, &l_b.as_array().to_owned(), &d_ijk);
 
         res.to_pyarray(py)
     }
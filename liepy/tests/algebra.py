@@ -0,0 +1,29 @@
+#!python
+
+import liepy as su
+import numpy as np
+
+basis = su.gen_gellmann(3)
+algebra = su.LieAlgebra(basis)
+
+assert algebra.dim == 8
+
+x = np.array([0.1, -0.4, 1.2, 0.3, 0, 2.1, -0.7, 0.9], dtype=np.complex128)
+y = np.array([1.0, 0.2, -0.3, 0.8, 0.5, -1.1, 0.4, 0], dtype=np.complex128)
+
+m_x = algebra.from_coordinates(x)
+m_y = algebra.from_coordinates(y)
+assert np.allclose(algebra.to_coordinates(m_x), x)
+
+commutator = m_x.dot(m_y) - m_y.dot(m_x)
+assert np.allclose(algebra.from_coordinates(algebra.bracket(x, y)), commutator)
+
+c, z = algebra.anticommutator(x, y)
+anticommutator = m_x.dot(m_y) + m_y.dot(m_x)
+assert np.allclose(algebra.from_coordinates(z) + c * np.eye(3), anticommutator)
+
+print(algebra.metric())
+
+g2 = su.LieAlgebra.from_cartan_type("G2")
+assert g2.dim == 14
+assert all(f == round(f.real) for f in g2.structure_constants().values())
@@ -0,0 +1,27 @@
+#!python
+
+import liepy as lp
+import numpy as np
+
+n = 3
+cases = [
+    (lp.gen_gl(n), n * n),
+    (lp.gen_sl(n), n * n - 1),
+    (lp.gen_so(n), n * (n - 1) // 2),
+    (lp.gen_sp(n), n * (2 * n + 1)),
+    (lp.gen_u(n), n * n),
+]
+
+for basis, dim in cases:
+    assert len(basis) == dim
+    f = lp.get_structure_constants(basis)
+    print(len(f))
+
+for x in lp.gen_so(4, 0.5):
+    assert np.isclose(np.trace(x.conj().T @ x).real, 0.5)
+
+# su(1, 1) and so(2, 1) have the same structure constants
+f_so21 = lp.get_structure_constants(lp.gen_so21())
+f_su11 = lp.get_structure_constants(lp.gen_su11())
+for key in set(f_so21) | set(f_su11):
+    assert np.isclose(f_so21.get(key, 0), f_su11.get(key, 0))
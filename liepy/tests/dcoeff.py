@@ -23,15 +23,16 @@ d_abc_exact |= {(i,i,7): -1/(2 * np.sqrt(3)) for i in range(3,8)}
 d_abc_exact |= {(2, i, i): 0.5 for i in range(3,5)} | {(2, i, i): -0.5 for i in range(5,7)}
 d_abc_exact |= {(1,3,6): -0.5, (0,3,5): 0.5, (0,4,6): 0.5, (1,4,5): 0.5}
 
-d_abc = su.get_d_coefficients(basis)
+# {T_a, T_b} = c_ab I + sum_c D_abc T_c, and for the Gell-Mann matrices D_abc = 2 d_abc
+d_abc, identity = su.get_d_tensor(basis)
 
 for (k, v) in sorted(d_abc.items()):
     perm = list(permutations(k))
     found = [p in d_abc_exact for p in perm]
     if any(found):
         i = found.index(True)
-        if not np.isclose(v, d_abc_exact[perm[i]]):
-            print(f"For {k} expected {d_abc_exact[perm[i]]}, got {v}")
+        if not np.isclose(v, 2 * d_abc_exact[perm[i]]):
+            print(f"For {k} expected {2 * d_abc_exact[perm[i]]}, got {v}")
         continue
     else:
         assert False, "Expect d coefficients don't match"
@@ -40,21 +41,14 @@ def anti(A,B):
     return A.dot(B) + B.dot(A)
 
 def anti_su(a,b):
-    def delta(i,j):
-        return 1 if i == j else 0
     I = np.eye(len(basis[a]))
 
     D = np.zeros_like(I, dtype=np.complex128)
-    
+
     for c in range(8):
-        perm = list(permutations((a,b,c)))
-        found = [p in d_abc for p in perm]
-        if any(found):
-            i = found.index(True)
-            
-            D += 2*d_abc[perm[i]] * basis[c]
+        D += d_abc.get((a, b, c), 0) * basis[c]
 
-    return delta(a,b) * I*4/3 + D
+    return identity[a, b] * I + D
 
 
 for a in range(8):
@@ -16,12 +16,20 @@ d_abc = su.get_d_coefficients(basis)
 f_ijk = su.get_structure_constants(basis)
 
 
-a = np.array([[1, 0, 0, 0, 0, 0, 0, 0]], dtype=np.complex128).T
-b = np.array([[0, 0, 0, 0, 0, 0, 0, 1]], dtype=np.complex128).T
+a = np.array([1, 0, 0, 0, 0, 0, 0, 0], dtype=np.complex128)
+b = np.array([0, 0, 0, 0, 0, 0, 0, 1], dtype=np.complex128)
 
 # 1,5; 1,6; 1,7; 1,8
 
 assert np.allclose(su.dot(a, b, d_abc), su.dot(b, a, d_abc))
+
+d_tensor, identity = su.get_d_tensor(basis)
+a_mat = sum(x * m for x, m in zip(a, basis))
+b_mat = sum(x * m for x, m in zip(b, basis))
+assert np.allclose(
+    su.su_anticommutator(a, b, d_tensor, identity, basis),
+    a_mat.dot(b_mat) + b_mat.dot(a_mat),
+)
 assert np.allclose(su.cross(a, b, f_ijk), -su.cross(b, a, f_ijk))
 
 print(su.dot(a,b, d_abc))
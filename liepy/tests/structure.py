@@ -7,4 +7,4 @@ basis = su.gen_gellmann(3)
 f_ijk = su.get_structure_constants(basis)
 print(f_ijk)
 
-print(f_ijk[(0,1)])
+print(f_ijk[(0,1,3)])
@@ -0,0 +1,73 @@
+use ndarray as nd;
+
+use ndarray_linalg::*;
+
+use crate::lie_algebra::{LieAlgebra, StructureConstants};
+
+///
+/// The adjoint representation of a Lie algebra, with one $m \times m$ matrix per basis element:
+///
+/// $$(\mathrm{ad}_{T_a})_{cb} = f_{abc}$$
+///
+/// so that $\mathrm{ad}_{T_a}$ acting on the coordinates of $Y$ gives the coordinates of $[T_a,
+/// Y]$.
+///
+pub fn adjoint_representation(algebra: &LieAlgebra) -> Vec<nd::Array2<c64>> {
+    ad_matrices(algebra.structure_constants())
+}
+
+// The matrices of the adjoint representation, from the structure constants alone
+pub(crate) fn ad_matrices(f: &StructureConstants) -> Vec<nd::Array2<c64>> {
+    let m = f.dim();
+
+    (0..m)
+        .map(|a| {
+            let mut ad = nd::Array2::zeros((m, m));
+            for b in 0..m {
+                for (c, f_abc) in f.pair(a, b) {
+                    ad[[*c, b]] = *f_abc;
+                }
+            }
+            ad
+        })
+        .collect()
+}
+
+///
+/// The matrix of $\mathrm{ad}_X = \sum_a x_a \mathrm{ad}_{T_a}$ for an element with coordinates
+/// $x$
+///
+pub fn adjoint_matrix(algebra: &LieAlgebra, x: &nd::Array1<c64>) -> nd::Array2<c64> {
+    let m = algebra.dim();
+    let mut ad = nd::Array2::zeros((m, m));
+    for ((a, b), f_ab) in algebra.structure_constants().pairs() {
+        if x[a].norm() == 0. {
+            continue;
+        }
+        for (c, f_abc) in f_ab {
+            ad[[*c, b]] += x[a] * f_abc;
+        }
+    }
+    ad
+}
+
+///
+/// The adjoint action $\mathrm{Ad}(U)$ of a group element on coordinates, defined by
+///
+/// $$U \Big(\sum_b x_b T_b\Big) U^{-1} = \sum_{c,b} \mathrm{Ad}(U)_{cb} x_b T_c$$
+///
+/// It preserves the Killing form. For SU(n) and an orthonormal anti-Hermitian basis such as
+/// `gen_gellmann`, it is a real orthogonal matrix, which gives the map SU(n) $\to$ SO(n²−1).
+///
+pub fn adjoint_action(algebra: &LieAlgebra, u: &nd::Array2<c64>) -> nd::Array2<c64> {
+    let u_inv = u.inv().expect("The group element is not invertible");
+
+    let columns: Vec<nd::Array1<c64>> = algebra
+        .basis()
+        .iter()
+        .map(|t_b| algebra.to_coordinates(&u.dot(t_b).dot(&u_inv)))
+        .collect();
+    let columns: Vec<_> = columns.iter().map(|x| x.view()).collect();
+
+    nd::stack(nd::Axis(1), columns.as_slice()).unwrap()
+}
@@ -0,0 +1,216 @@
+use ndarray as nd;
+
+use ndarray_linalg::*;
+
+use crate::lie_algebra::{contract, StructureConstants};
+
+///
+/// A truncated series expansion in the algebra, with an error estimate for each truncation
+///
+#[derive(Clone, Debug)]
+pub struct Expansion {
+    /// The coordinates of the terms of the series, by increasing order
+    pub terms: Vec<nd::Array1<c64>>,
+    /// The error of the series truncated after the first k + 1 terms is estimated by the norm
+    /// of the coordinates of the leading omitted term, `errors[k]`
+    pub errors: Vec<f64>,
+}
+
+impl Expansion {
+    /// The sum of every term
+    pub fn sum(&self) -> nd::Array1<c64> {
+        let mut res = nd::Array1::zeros(self.terms[0].len());
+        for x in self.terms.iter() {
+            res += x;
+        }
+        res
+    }
+}
+
+///
+/// The Baker–Campbell–Hausdorff series $Z = \log(e^X e^Y)$ up to terms of degree `order` in
+/// $X$ and $Y$, from the coordinates of $X$ and $Y$ and the structure constants of the algebra.
+/// The series is computed with the recursion of Varadarajan, with $Z_1 = X + Y$ and
+/// $$(n + 1) Z_{n + 1} = \frac{1}{2} [X - Y, Z_n] + \sum_{p \geq 1, 2p \leq n} \frac{B_{2p}}{(2p)!}
+/// \sum_{k_1 + \cdots + k_{2p} = n} [Z_{k_1}, [\cdots, [Z_{k_{2p}}, X + Y] \cdots]]$$
+/// where $B_{2p}$ are the Bernoulli numbers, so only nested brackets of coordinates are needed.
+///
+/// `terms[n - 1]` is the homogeneous term $Z_n$ of degree n, and the error estimate for the
+/// truncation at degree n is the norm of $Z_{n + 1}$. The series converges for
+/// $\|X\| + \|Y\| < \log 2$ in any norm with $\|[X, Y]\| \leq \|X\| \|Y\|$.
+///
+pub fn bch(
+    x: &nd::Array1<c64>,
+    y: &nd::Array1<c64>,
+    order: usize,
+    f: &StructureConstants,
+) -> Expansion {
+    assert!(order > 0, "The order has to be positive");
+    let z = graded_bch(&graded(x, 1, order + 1), &graded(y, 1, order + 1), f);
+    Expansion {
+        terms: z[1..=order].to_vec(),
+        errors: z[2..].iter().map(norm).collect(),
+    }
+}
+
+///
+/// The Zassenhaus formula $e^{X + Y} = e^X e^Y e^{C_2} e^{C_3} \cdots$ up to the factor
+/// $C_{order}$ of degree `order` in $X$ and $Y$, from the coordinates of $X$ and $Y$ and the
+/// structure constants of the algebra, e.g. $C_2 = -\frac{1}{2} [X, Y]$.
+///
+/// The factors are peeled off one degree at a time from $W = \log(e^{-Y} e^{-X} e^{X + Y})$,
+/// with $C_n$ the term of degree n of $W$ and $W \to \log(e^{-C_n} e^W)$, using only the
+/// Baker–Campbell–Hausdorff series on coordinates.
+///
+/// `terms` is $[C_2, \ldots, C_{order}]$, to be multiplied after $e^X e^Y$. The error estimate
+/// for the truncation at degree n, with `errors[n - 1]` for n from 1 to `order`, is the norm of
+/// $C_{n + 1}$.
+///
+pub fn zassenhaus(
+    x: &nd::Array1<c64>,
+    y: &nd::Array1<c64>,
+    order: usize,
+    f: &StructureConstants,
+) -> Expansion {
+    assert!(order > 0, "The order has to be positive");
+    let max_degree = order + 1;
+    let x = graded(x, 1, max_degree);
+    let y = graded(y, 1, max_degree);
+    let sum: Vec<nd::Array1<c64>> = x.iter().zip(y.iter()).map(|(a, b)| a + b).collect();
+
+    let minus_x: Vec<nd::Array1<c64>> = x.iter().map(|a| -a).collect();
+    let minus_y: Vec<nd::Array1<c64>> = y.iter().map(|a| -a).collect();
+    let mut w = graded_bch(&minus_y, &graded_bch(&minus_x, &sum, f), f);
+
+    let mut factors = vec![];
+    for n in 2..=max_degree {
+        let c = w[n].clone();
+        let minus_c: Vec<nd::Array1<c64>> = graded(&c, n, max_degree).iter().map(|a| -a).collect();
+        w = graded_bch(&minus_c, &w, f);
+        factors.push(c);
+    }
+
+    Expansion {
+        terms: factors[..order - 1].to_vec(),
+        errors: factors.iter().map(norm).collect(),
+    }
+}
+
+// The norm of the coordinates
+fn norm(x: &nd::Array1<c64>) -> f64 {
+    x.iter().map(|x| x.norm_sqr()).sum::<f64>().sqrt()
+}
+
+// A series truncated after degree max_degree, stored by degree from 0, with the single term x of
+// the given degree
+fn graded(x: &nd::Array1<c64>, degree: usize, max_degree: usize) -> Vec<nd::Array1<c64>> {
+    let mut res = vec![nd::Array1::zeros(x.len()); max_degree + 1];
+    res[degree] = x.clone();
+    res
+}
+
+fn bracket(
+    a: &[nd::Array1<c64>],
+    b: &[nd::Array1<c64>],
+    f: &StructureConstants,
+) -> Vec<nd::Array1<c64>> {
+    let max_degree = a.len() - 1;
+    let mut res = vec![nd::Array1::zeros(a[0].len()); max_degree + 1];
+    for (i, a_i) in a.iter().enumerate() {
+        if a_i.iter().all(|x| x.norm() == 0.) {
+            continue;
+        }
+        let a_i: Vec<c64> = a_i.iter().cloned().collect();
+        for (j, b_j) in b.iter().enumerate().take(max_degree + 1 - i) {
+            if b_j.iter().all(|x| x.norm() == 0.) {
+                continue;
+            }
+            let b_j: Vec<c64> = b_j.iter().cloned().collect();
+            res[i + j] += &nd::Array1::from(contract(&a_i, &b_j, f));
+        }
+    }
+    res
+}
+
+// log(e^a e^b) for two truncated series without a term of degree 0
+fn graded_bch(
+    a: &[nd::Array1<c64>],
+    b: &[nd::Array1<c64>],
+    f: &StructureConstants,
+) -> Vec<nd::Array1<c64>> {
+    let max_degree = a.len() - 1;
+    let add = |x: &mut Vec<nd::Array1<c64>>, y: &[nd::Array1<c64>], c: f64| {
+        for (x, y) in x.iter_mut().zip(y.iter()) {
+            x.scaled_add(c64::new(c, 0.), y);
+        }
+    };
+    let mut sum = a.to_vec();
+    add(&mut sum, b, 1.);
+    let mut difference = a.to_vec();
+    add(&mut difference, b, -1.);
+
+    // The terms z_n are brackets of n elements, so have degree at least n
+    let coefficients = bernoulli_coefficients(max_degree);
+    let mut z: Vec<Vec<nd::Array1<c64>>> = vec![vec![], sum.clone()];
+    // nested[m][k] is the sum over k_1 + ... + k_m = k of [z_{k_1}, [..., [z_{k_m}, a + b]]]
+    let mut nested: Vec<Vec<Vec<nd::Array1<c64>>>> = vec![vec![]; max_degree + 1];
+    for n in 1..max_degree {
+        nested[1].push(bracket(&z[n], &sum, f));
+        for m in 2..=n {
+            let mut res = vec![nd::Array1::zeros(a[0].len()); max_degree + 1];
+            for k in 1..=(n - m + 1) {
+                add(
+                    &mut res,
+                    &bracket(&z[k], &nested[m - 1][n - k - (m - 1)], f),
+                    1.,
+                );
+            }
+            nested[m].push(res);
+        }
+
+        let mut next = bracket(&difference, &z[n], f);
+        for x in next.iter_mut() {
+            *x *= c64::new(0.5, 0.);
+        }
+        for p in 1..=(n / 2) {
+            add(&mut next, &nested[2 * p][n - 2 * p], coefficients[p]);
+        }
+        for x in next.iter_mut() {
+            *x /= c64::new((n + 1) as f64, 0.);
+        }
+        z.push(next);
+    }
+
+    let mut res = vec![nd::Array1::zeros(a[0].len()); max_degree + 1];
+    for z_n in z.iter().skip(1) {
+        add(&mut res, z_n, 1.);
+    }
+    res
+}
+
+// B_{2p} / (2p)! for p from 0 to n / 2, from the recurrence of the Bernoulli numbers
+fn bernoulli_coefficients(n: usize) -> Vec<f64> {
+    let mut binomial = vec![vec![1.; n + 2]; n + 2];
+    for i in 1..(n + 2) {
+        for k in 1..i {
+            binomial[i][k] = binomial[i - 1][k - 1] + binomial[i - 1][k];
+        }
+    }
+    let mut bernoulli = vec![1.];
+    for m in 1..=n {
+        let s: f64 = (0..m).map(|k| binomial[m + 1][k] * bernoulli[k]).sum();
+        bernoulli.push(-s / (m + 1) as f64);
+    }
+
+    let mut factorial = 1.;
+    let mut res = vec![];
+    for (k, b) in bernoulli.iter().enumerate() {
+        if k > 0 {
+            factorial *= k as f64;
+        }
+        if k % 2 == 0 {
+            res.push(b / factorial);
+        }
+    }
+    res
+}
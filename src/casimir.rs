@@ -0,0 +1,128 @@
+use ndarray as nd;
+
+use ndarray_linalg::*;
+
+use crate::lie_algebra::LieAlgebra;
+
+///
+/// A Casimir operator in some representation, together with its eigenvalue when the operator is
+/// proportional to the identity (as it is in any irreducible representation)
+///
+#[derive(Clone, Debug)]
+pub struct Casimir {
+    pub operator: nd::Array2<c64>,
+    pub eigenvalue: Option<c64>,
+}
+
+///
+/// The Casimir operator of the given order in the representation spanned by the basis of the
+/// algebra itself. See [`casimir_in_representation`].
+///
+pub fn casimir(algebra: &LieAlgebra, order: usize) -> Casimir {
+    casimir_in_representation(algebra, algebra.basis(), order)
+}
+
+///
+/// The Casimir operator of the given order, where `representation[a]` represents the basis
+/// element $T_a$ of the algebra.
+///
+/// Indices are raised with the inverse Killing form $g^{ab}$, so the algebra has to be
+/// semisimple. The quadratic Casimir is
+///
+/// $$C_2 = \sum_{a,b} g^{ab} R_a R_b$$
+///
+/// and the higher ones are built from the symmetric d-tensor of the algebra, $\{T_a, T_b\} =
+/// c_{ab} I + \sum_c d_{ab}^{\ \ c} T_c$:
+///
+/// $$C_3 = \sum d^{abc} R_a R_b R_c, \quad C_k = \sum d^{a_1 a_2 e_1} d_{e_1}^{\ \ a_3 e_2} \cdots
+/// d_{e_{k-3}}^{\ \ a_{k-1} a_k} R_{a_1} \cdots R_{a_k}$$
+///
+/// For su(2) the d-tensor vanishes, and so do all Casimirs above the quadratic one.
+///
+pub fn casimir_in_representation(
+    algebra: &LieAlgebra,
+    representation: &[nd::Array2<c64>],
+    order: usize,
+) -> Casimir {
+    assert!(order >= 2, "There are no Casimirs of order below 2");
+    assert_eq!(
+        representation.len(),
+        algebra.dim(),
+        "The representation needs one matrix per basis element"
+    );
+
+    let n = representation[0].shape()[0];
+    let g_inv = algebra
+        .killing_form()
+        .inv()
+        .expect("The Killing form is degenerate, the algebra is not semisimple");
+
+    // R^a = sum_b g^ab R_b
+    let raised: Vec<nd::Array2<c64>> = (0..algebra.dim())
+        .map(|a| {
+            let mut r = nd::Array2::zeros((n, n));
+            for (b, r_b) in representation.iter().enumerate() {
+                r.scaled_add(g_inv[[a, b]], r_b);
+            }
+            r
+        })
+        .collect();
+
+    let operator = if order == 2 {
+        let mut c = nd::Array2::zeros((n, n));
+        for (r_up, r) in raised.iter().zip(representation) {
+            c = c + r_up.dot(r);
+        }
+        c
+    } else {
+        let d = &algebra.d_tensor().d;
+
+        // X^e = sum d_ab^e R^a R^b
+        let mut x = vec![nd::Array2::zeros((n, n)); algebra.dim()];
+        for (a, b, e, d_abe) in d.iter() {
+            x[e].scaled_add(d_abe, &raised[a].dot(&raised[b]));
+        }
+
+        // X^e' = sum d_eb^e' X^e R^b, once for every d-tensor in the middle of the chain
+        for _ in 4..order {
+            let mut next = vec![nd::Array2::zeros((n, n)); algebra.dim()];
+            for (e, b, f, d_ebf) in d.iter() {
+                next[f].scaled_add(d_ebf, &x[e].dot(&raised[b]));
+            }
+            x = next;
+        }
+
+        let mut c = nd::Array2::zeros((n, n));
+        if order == 3 {
+            for (x_e, r_e) in x.iter().zip(representation) {
+                c = c + x_e.dot(r_e);
+            }
+        } else {
+            // The last d-tensor, sum d_ea^c X^e R^a R_c
+            for (e, a, f, d_eaf) in d.iter() {
+                c.scaled_add(d_eaf, &x[e].dot(&raised[a]).dot(&representation[f]));
+            }
+        }
+        c
+    };
+
+    let eigenvalue = proportional_to_identity(&operator);
+    Casimir {
+        operator,
+        eigenvalue,
+    }
+}
+
+// The constant c if the matrix is c I
+fn proportional_to_identity(m: &nd::Array2<c64>) -> Option<c64> {
+    use approx::AbsDiffEq;
+
+    let n = m.shape()[0];
+    let c = m.diag().sum() / n as f64;
+    let identity: nd::Array2<c64> = nd::Array2::eye(n);
+    if m.abs_diff_eq(&(identity * c), 1e-8) {
+        Some(c)
+    } else {
+        None
+    }
+}
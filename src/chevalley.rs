@@ -0,0 +1,486 @@
+use ndarray as nd;
+
+use ndarray_linalg::*;
+
+use std::collections::HashMap;
+
+use crate::classify::CartanType;
+use crate::lie_algebra::StructureConstants;
+
+///
+/// The Chevalley basis of a simple Lie algebra, built from its Cartan matrix alone.
+///
+/// The basis is made of a root vector $e_\alpha$ for every root and of the coroots $h_i =
+/// [e_{\alpha_i}, e_{-\alpha_i}]$ of the simple roots, in the order
+///
+/// $$e_{\beta_1}, \dots, e_{\beta_N}, h_1, \dots, h_r, e_{-\beta_1}, \dots, e_{-\beta_N}$$
+///
+/// where $\beta_1, \dots, \beta_N$ are the positive roots by increasing height. The brackets are
+///
+/// $$[h_i, e_\alpha] = \langle \alpha, \alpha_i^\vee \rangle e_\alpha, \quad [e_\alpha,
+/// e_{-\alpha}] = h_\alpha, \quad [e_\alpha, e_\beta] = N_{\alpha, \beta} e_{\alpha + \beta}$$
+///
+/// with integers $N_{\alpha, \beta} = \pm (p + 1)$, where $p$ is the largest integer such that
+/// $\beta - p \alpha$ is a root.
+///
+/// The positive roots are generated from the simple ones with the root strings allowed by the
+/// Serre relations $\mathrm{ad}_{e_i}^{1 - A_{ji}} e_j = 0$. The signs of $N_{\alpha, \beta}$ are
+/// fixed by taking $N_{\alpha, \beta} > 0$ for every extraspecial pair, and all other structure
+/// constants follow exactly from the relations between them (Carter, _Simple Groups of Lie
+/// Type_, ch. 4).
+///
+#[derive(Clone, Debug)]
+pub struct ChevalleyBasis {
+    pub cartan_type: CartanType,
+    pub cartan_matrix: nd::Array2<i32>,
+    /// Positive roots as coefficients of the simple roots, by increasing height
+    pub positive_roots: Vec<Vec<i32>>,
+    /// Exact structure constants, as (a, b, c, f_abc) with $[x_a, x_b] = \sum_c f_{abc} x_c$
+    pub structure_constants: Vec<(usize, usize, usize, i32)>,
+    roots: Roots,
+}
+
+impl ChevalleyBasis {
+    pub fn new(cartan_type: CartanType) -> Self {
+        let cartan_matrix = cartan_type.cartan_matrix();
+        let roots = Roots::new(&cartan_matrix);
+
+        let mut basis = ChevalleyBasis {
+            cartan_type,
+            cartan_matrix,
+            positive_roots: roots.positive.clone(),
+            structure_constants: Vec::new(),
+            roots,
+        };
+        basis.structure_constants = basis.find_structure_constants();
+        basis
+    }
+
+    /// Number of basis elements
+    pub fn dim(&self) -> usize {
+        2 * self.positive_roots.len() + self.rank()
+    }
+
+    pub fn rank(&self) -> usize {
+        self.cartan_type.rank()
+    }
+
+    /// Position of $h_i$ in the basis
+    pub fn cartan_index(&self, i: usize) -> usize {
+        assert!(i < self.rank(), "There is no simple root {}", i);
+        self.positive_roots.len() + i
+    }
+
+    /// Position of $e_\alpha$ in the basis, for a positive or negative root given by its
+    /// coefficients in the simple roots
+    pub fn root_index(&self, root: &[i32]) -> Option<usize> {
+        let n = self.positive_roots.len();
+        match self.roots.find(root)? {
+            (k, true) => Some(k),
+            (k, false) => Some(n + self.rank() + k),
+        }
+    }
+
+    /// The root of the basis element `a`, or `None` for the Cartan elements $h_i$
+    pub fn root(&self, a: usize) -> Option<Vec<i32>> {
+        let n = self.positive_roots.len();
+        let r = self.rank();
+        if a < n {
+            Some(self.positive_roots[a].clone())
+        } else if a >= n + r {
+            Some(neg(&self.positive_roots[a - n - r]))
+        } else {
+            None
+        }
+    }
+
+    /// The structure constant $N_{\alpha, \beta}$, zero when $\alpha + \beta$ is not a root
+    pub fn n(&self, alpha: &[i32], beta: &[i32]) -> i32 {
+        self.roots.n(alpha, beta)
+    }
+
+    /// The structure constants as a complex tensor
+    pub fn to_structure_constants(&self) -> StructureConstants {
+        StructureConstants::from_entries(
+            self.dim(),
+            self.structure_constants
+                .iter()
+                .map(|(a, b, c, x)| (*a, *b, *c, c64::new(*x as f64, 0.))),
+        )
+    }
+
+    ///
+    /// The adjoint representation, $(\mathrm{ad}_{x_a})_{cb} = f_{abc}$, which is faithful and
+    /// has integer entries
+    ///
+    pub fn adjoint_representation(&self) -> Vec<nd::Array2<c64>> {
+        let m = self.dim();
+        let mut ad = vec![nd::Array2::zeros((m, m)); m];
+        for (a, b, c, x) in self.structure_constants.iter() {
+            ad[*a][[*c, *b]] = c64::new(*x as f64, 0.);
+        }
+        ad
+    }
+
+    ///
+    /// The defining representation of the classical algebras, by matrices with integer entries:
+    /// sl(n + 1) for $A_n$, so(2n + 1) for $B_n$, sp(2n) for $C_n$ and so(2n) for $D_n$. The
+    /// orthogonal and symplectic algebras preserve the forms
+    ///
+    /// $$\begin{pmatrix} 0 & I & 0 \\\\ I & 0 & 0 \\\\ 0 & 0 & 2 \end{pmatrix}, \quad
+    /// \begin{pmatrix} 0 & I \\\\ -I & 0 \end{pmatrix}, \quad \begin{pmatrix} 0 & I \\\\ I & 0
+    /// \end{pmatrix}$$
+    ///
+    /// Returns `None` for the exceptional algebras.
+    ///
+    pub fn defining_representation(&self) -> Option<Vec<nd::Array2<c64>>> {
+        let n = self.rank();
+        let unit = |size: usize, entries: &[(usize, usize, i32)]| {
+            let mut m = nd::Array2::<i32>::zeros((size, size));
+            for (i, j, x) in entries {
+                m[[*i, *j]] += x;
+            }
+            m
+        };
+        // e_i and f_i of a root e_i - e_(i+1), acting on the first n coordinates and, with the
+        // opposite weights, on the next n
+        let long = |size: usize, i: usize| {
+            (
+                unit(size, &[(i, i + 1, 1), (n + i + 1, n + i, -1)]),
+                unit(size, &[(i + 1, i, 1), (n + i, n + i + 1, -1)]),
+            )
+        };
+
+        let simple: Vec<(nd::Array2<i32>, nd::Array2<i32>)> = match self.cartan_type.series() {
+            'A' => (0..n)
+                .map(|i| (unit(n + 1, &[(i, i + 1, 1)]), unit(n + 1, &[(i + 1, i, 1)])))
+                .collect(),
+            'B' => {
+                let mut x: Vec<_> = (0..n - 1).map(|i| long(2 * n + 1, i)).collect();
+                x.push((
+                    unit(2 * n + 1, &[(n - 1, 2 * n, 2), (2 * n, 2 * n - 1, -1)]),
+                    unit(2 * n + 1, &[(2 * n, n - 1, 1), (2 * n - 1, 2 * n, -2)]),
+                ));
+                x
+            }
+            'C' => {
+                let mut x: Vec<_> = (0..n - 1).map(|i| long(2 * n, i)).collect();
+                x.push((
+                    unit(2 * n, &[(n - 1, 2 * n - 1, 1)]),
+                    unit(2 * n, &[(2 * n - 1, n - 1, 1)]),
+                ));
+                x
+            }
+            'D' => {
+                let mut x: Vec<_> = (0..n - 1).map(|i| long(2 * n, i)).collect();
+                x.push((
+                    unit(2 * n, &[(n - 2, 2 * n - 1, 1), (n - 1, 2 * n - 2, -1)]),
+                    unit(2 * n, &[(2 * n - 1, n - 2, 1), (2 * n - 2, n - 1, -1)]),
+                ));
+                x
+            }
+            _ => return None,
+        };
+
+        let rep = self.extend_representation(&simple);
+        Some(
+            rep.iter()
+                .map(|x| x.mapv(|x| c64::new(x as f64, 0.)))
+                .collect(),
+        )
+    }
+
+    // Every basis element of a representation, from the matrices of the simple e_i and f_i,
+    // through e_(alpha + beta) = [e_alpha, e_beta] / N_(alpha, beta) on extraspecial pairs
+    fn extend_representation(
+        &self,
+        simple: &[(nd::Array2<i32>, nd::Array2<i32>)],
+    ) -> Vec<nd::Array2<i32>> {
+        let bracket = |x: &nd::Array2<i32>, y: &nd::Array2<i32>| x.dot(y) - y.dot(x);
+        let n = self.positive_roots.len();
+
+        // The simple roots come first
+        let mut positive: Vec<nd::Array2<i32>> = simple.iter().map(|x| x.0.clone()).collect();
+        let mut negative: Vec<nd::Array2<i32>> = simple.iter().map(|x| x.1.clone()).collect();
+        for k in self.rank()..n {
+            let (alpha, beta) = self.roots.extraspecial[k].unwrap();
+            let (a, b) = (&self.positive_roots[alpha], &self.positive_roots[beta]);
+
+            let e = bracket(&positive[alpha], &positive[beta]) / self.n(a, b);
+            let f = bracket(&negative[alpha], &negative[beta]) / self.n(&neg(a), &neg(b));
+            positive.push(e);
+            negative.push(f);
+        }
+
+        let mut rep = positive;
+        rep.extend(simple.iter().map(|(e, f)| bracket(e, f)));
+        rep.extend(negative);
+        rep
+    }
+
+    fn find_structure_constants(&self) -> Vec<(usize, usize, usize, i32)> {
+        let m = self.dim();
+        let r = self.rank();
+        let lengths = &self.roots.lengths;
+
+        // <alpha, alpha_i^vee>
+        let pairing = |alpha: &[i32], i: usize| -> i32 {
+            alpha
+                .iter()
+                .enumerate()
+                .map(|(j, a_j)| a_j * self.cartan_matrix[[j, i]])
+                .sum()
+        };
+
+        let mut f = Vec::new();
+        for a in 0..m {
+            for b in 0..m {
+                match (self.root(a), self.root(b)) {
+                    (Some(x), Some(y)) => {
+                        let z = add(&x, &y);
+                        if z.iter().all(|z_i| *z_i == 0) {
+                            // The coroot h_x = sum_i x_i (alpha_i, alpha_i) / (x, x) h_i
+                            let length = self.roots.inner(&x, &x);
+                            for i in 0..r {
+                                if x[i] != 0 {
+                                    let c = x[i] * lengths[i];
+                                    assert_eq!(c % length, 0);
+                                    f.push((a, b, self.cartan_index(i), c / length));
+                                }
+                            }
+                        } else {
+                            let n_xy = self.n(&x, &y);
+                            if n_xy != 0 {
+                                f.push((a, b, self.root_index(&z).unwrap(), n_xy));
+                            }
+                        }
+                    }
+                    (None, Some(y)) => {
+                        let i = a - self.positive_roots.len();
+                        let x = pairing(&y, i);
+                        if x != 0 {
+                            f.push((a, b, b, x));
+                        }
+                    }
+                    (Some(x), None) => {
+                        let i = b - self.positive_roots.len();
+                        let x = pairing(&x, i);
+                        if x != 0 {
+                            f.push((a, b, a, -x));
+                        }
+                    }
+                    (None, None) => {}
+                }
+            }
+        }
+        f
+    }
+}
+
+fn add(x: &[i32], y: &[i32]) -> Vec<i32> {
+    x.iter().zip(y).map(|(a, b)| a + b).collect()
+}
+
+fn neg(x: &[i32]) -> Vec<i32> {
+    x.iter().map(|a| -a).collect()
+}
+
+// The root system of a Cartan matrix, with the structure constants N_(alpha, beta) of pairs of
+// positive roots
+#[derive(Clone, Debug)]
+struct Roots {
+    positive: Vec<Vec<i32>>,
+    index: HashMap<Vec<i32>, usize>,
+    // (alpha_i, alpha_i), with the short roots of length 2
+    lengths: Vec<i32>,
+    // (alpha_i, alpha_j)
+    form: nd::Array2<i32>,
+    // For every positive root of height above 1, its extraspecial pair
+    extraspecial: Vec<Option<(usize, usize)>>,
+    n: HashMap<(usize, usize), i32>,
+}
+
+impl Roots {
+    fn new(cartan_matrix: &nd::Array2<i32>) -> Self {
+        let r = cartan_matrix.shape()[0];
+
+        // A_ij (alpha_j, alpha_j) = A_ji (alpha_i, alpha_i) on a connected diagram
+        let mut lengths = vec![0.; r];
+        lengths[0] = 1.;
+        let mut queue = vec![0];
+        while let Some(i) = queue.pop() {
+            for j in 0..r {
+                if cartan_matrix[[i, j]] != 0 && lengths[j] == 0. {
+                    lengths[j] =
+                        lengths[i] * cartan_matrix[[j, i]] as f64 / cartan_matrix[[i, j]] as f64;
+                    queue.push(j);
+                }
+            }
+        }
+        let shortest = lengths.iter().cloned().fold(f64::INFINITY, f64::min);
+        let lengths: Vec<i32> = lengths
+            .iter()
+            .map(|x| (2. * x / shortest).round() as i32)
+            .collect();
+        let form =
+            nd::Array2::from_shape_fn((r, r), |(i, j)| cartan_matrix[[i, j]] * lengths[j] / 2);
+
+        let mut roots = Roots {
+            positive: Vec::new(),
+            index: HashMap::new(),
+            lengths,
+            form,
+            extraspecial: Vec::new(),
+            n: HashMap::new(),
+        };
+
+        // Root strings: beta + alpha_i is a root when q - <beta, alpha_i^vee> > 0, with q the
+        // number of times alpha_i can be subtracted from beta
+        for i in 0..r {
+            let mut alpha = vec![0; r];
+            alpha[i] = 1;
+            roots.push(alpha);
+        }
+        let mut k = 0;
+        while k < roots.positive.len() {
+            let beta = roots.positive[k].clone();
+            for i in 0..r {
+                let mut q = 0;
+                let mut x = beta.clone();
+                loop {
+                    x[i] -= 1;
+                    if roots.index.contains_key(&x) {
+                        q += 1;
+                    } else {
+                        break;
+                    }
+                }
+                let pairing: i32 = (0..r).map(|j| beta[j] * cartan_matrix[[j, i]]).sum();
+                if q - pairing > 0 {
+                    let mut next = beta.clone();
+                    next[i] += 1;
+                    if !roots.index.contains_key(&next) {
+                        roots.push(next);
+                    }
+                }
+            }
+            k += 1;
+        }
+
+        roots.find_n();
+        roots
+    }
+
+    fn push(&mut self, root: Vec<i32>) {
+        self.index.insert(root.clone(), self.positive.len());
+        self.positive.push(root);
+    }
+
+    fn inner(&self, x: &[i32], y: &[i32]) -> i32 {
+        let r = x.len();
+        (0..r)
+            .flat_map(|i| (0..r).map(move |j| (i, j)))
+            .map(|(i, j)| x[i] * self.form[[i, j]] * y[j])
+            .sum()
+    }
+
+    // The position of a root among the positive roots, and whether it is positive
+    fn find(&self, x: &[i32]) -> Option<(usize, bool)> {
+        if let Some(k) = self.index.get(x) {
+            Some((*k, true))
+        } else {
+            self.index.get(&neg(x)).map(|k| (*k, false))
+        }
+    }
+
+    fn is_root(&self, x: &[i32]) -> bool {
+        self.find(x).is_some()
+    }
+
+    // N_(x, y) for any two roots, from those of positive roots through
+    //   N_(-x, -y) = -N_(x, y)
+    //   N_(x, y) / (z, z) = N_(y, z) / (x, x) = N_(z, x) / (y, y)  when x + y + z = 0
+    fn n(&self, x: &[i32], y: &[i32]) -> i32 {
+        let z = add(x, y);
+        if !self.is_root(&z) {
+            return 0;
+        }
+        let (k_x, x_positive) = self.find(x).unwrap();
+        let (k_y, y_positive) = self.find(y).unwrap();
+        let (_, z_positive) = self.find(&z).unwrap();
+
+        let ratio = |n: i32, num: &[i32], den: &[i32]| {
+            let x = n * self.inner(num, num);
+            let den = self.inner(den, den);
+            assert_eq!(x % den, 0);
+            x / den
+        };
+
+        match (x_positive, y_positive) {
+            (true, true) => self.n[&(k_x, k_y)],
+            (false, false) => -self.n[&(k_x, k_y)],
+            (true, false) if z_positive => ratio(-self.n(&neg(y), &z), &z, x),
+            (true, false) => ratio(self.n(&neg(&z), x), &z, y),
+            (false, true) => -self.n(y, x),
+        }
+    }
+
+    fn find_n(&mut self) {
+        let count = self.positive.len();
+        self.extraspecial = vec![None; count];
+
+        for k in 0..count {
+            let xi = self.positive[k].clone();
+            // Special pairs (gamma, delta) with gamma < delta in the order of the roots
+            let special: Vec<(usize, usize)> = (0..k)
+                .filter_map(|gamma| {
+                    let delta = add(&xi, &neg(&self.positive[gamma]));
+                    self.index
+                        .get(&delta)
+                        .filter(|delta| gamma < **delta)
+                        .map(|delta| (gamma, *delta))
+                })
+                .collect();
+            let (alpha, beta) = match special.first() {
+                Some(x) => *x,
+                None => continue,
+            };
+            self.extraspecial[k] = Some((alpha, beta));
+
+            let a = self.positive[alpha].clone();
+            let b = self.positive[beta].clone();
+            let mut p = 0;
+            while self.is_root(&add(
+                &b,
+                &a.iter().map(|x| -(p + 1) * x).collect::<Vec<_>>(),
+            )) {
+                p += 1;
+            }
+            self.n.insert((alpha, beta), p + 1);
+            self.n.insert((beta, alpha), -(p + 1));
+
+            // The other special pairs from the relation between alpha, beta, -gamma and -delta
+            let n_ab = (p + 1) as f64;
+            let length = self.inner(&xi, &xi) as f64;
+            for (gamma, delta) in special.iter().skip(1) {
+                let c = neg(&self.positive[*gamma]);
+                let d = neg(&self.positive[*delta]);
+
+                let term = |x: &[i32], y: &[i32], u: &[i32], v: &[i32]| {
+                    let s = add(x, y);
+                    if self.is_root(&s) {
+                        (self.n(x, y) * self.n(u, v)) as f64 / self.inner(&s, &s) as f64
+                    } else {
+                        0.
+                    }
+                };
+                let x = length / n_ab * (term(&b, &c, &a, &d) + term(&c, &a, &b, &d));
+                let n_cd = x.round() as i32;
+                assert!((x - n_cd as f64).abs() < 1e-8);
+
+                self.n.insert((*gamma, *delta), n_cd);
+                self.n.insert((*delta, *gamma), -n_cd);
+            }
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use ndarray as nd;
+use num_complex::Complex64;
+
+///
+/// How the matrices of a basis are scaled
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Normalization {
+    /// Matrices with integer entries, such as $E_{ij}$ and $E_{ij} - E_{ji}$
+    #[default]
+    Unit,
+    /// $\mathrm{Tr}(T_a^\dagger T_a) = c$, e.g. 2 as for the Gell-Mann matrices, or 1/2 as for
+    /// $\lambda_a / 2$
+    Trace(f64),
+}
+
+fn normalize(
+    basis: Vec<nd::Array2<Complex64>>,
+    normalization: Normalization,
+) -> Vec<nd::Array2<Complex64>> {
+    match normalization {
+        Normalization::Unit => basis,
+        Normalization::Trace(c) => basis
+            .into_iter()
+            .map(|x| {
+                let norm: f64 = x.iter().map(|x| x.norm_sqr()).sum();
+                x * Complex64::new((c / norm).sqrt(), 0.)
+            })
+            .collect(),
+    }
+}
+
+// The matrix with the given (row, column, value) entries
+fn matrix(n: usize, entries: &[(usize, usize, f64)]) -> nd::Array2<Complex64> {
+    let mut m = nd::Array2::zeros((n, n));
+    for (i, j, x) in entries {
+        m[[*i, *j]] += Complex64::new(*x, 0.);
+    }
+    m
+}
+
+///
+/// Returns a basis of gl(n), the unit matrices $E_{jk}$ in row-major order
+///
+pub fn gen_gl(n: usize, normalization: Normalization) -> Vec<nd::Array2<Complex64>> {
+    let mut basis = Vec::with_capacity(n * n);
+    for j in 0..n {
+        for k in 0..n {
+            basis.push(matrix(n, &[(j, k, 1.)]));
+        }
+    }
+    normalize(basis, normalization)
+}
+
+///
+/// Returns a basis of sl(n), in the same order as `gen_gellmann`: the unit matrices $E_{jk}$ for
+/// $j \neq k$, and at the position of $E_{ll}$ the diagonal matrix
+///
+/// $$D_l = \mathrm{diag}(1, \dots, 1, -l, 0, \dots, 0)$$
+///
+/// with $l$ ones, for $1 \leq l < n$. All of them are orthogonal under $\mathrm{Tr}(A^\dagger
+/// B)$. The basis is empty for $n \leq 1$.
+///
+pub fn gen_sl(n: usize, normalization: Normalization) -> Vec<nd::Array2<Complex64>> {
+    let mut basis = Vec::with_capacity((n * n).saturating_sub(1));
+    for j in 0..n {
+        for k in 0..n {
+            if j != k {
+                basis.push(matrix(n, &[(j, k, 1.)]));
+            } else if j + 1 < n {
+                let mut entries: Vec<(usize, usize, f64)> = (0..=j).map(|p| (p, p, 1.)).collect();
+                entries.push((j + 1, j + 1, -(j as f64 + 1.)));
+                basis.push(matrix(n, &entries));
+            }
+        }
+    }
+    normalize(basis, normalization)
+}
+
+///
+/// Returns a basis of so(n), the real antisymmetric matrices $E_{jk} - E_{kj}$ for $j < k$ in
+/// lexicographic order. The basis is empty for $n \leq 1$.
+///
+pub fn gen_so(n: usize, normalization: Normalization) -> Vec<nd::Array2<Complex64>> {
+    let mut basis = Vec::with_capacity(n * n.saturating_sub(1) / 2);
+    for j in 0..n {
+        for k in j + 1..n {
+            basis.push(matrix(n, &[(j, k, 1.), (k, j, -1.)]));
+        }
+    }
+    normalize(basis, normalization)
+}
+
+///
+/// Returns a basis of sp(2n), the real Hamiltonian matrices $X$ with $X^T J + J X = 0$ for
+///
+/// $$J = \begin{pmatrix} 0 & I \\\\ -I & 0 \end{pmatrix}, \quad X = \begin{pmatrix} A & B \\\\ C
+/// & -A^T \end{pmatrix}$$
+///
+/// where $B$ and $C$ are symmetric. The basis is made of the $n^2$ matrices with $A = E_{jk}$,
+/// then the $n (n + 1) / 2$ with $B = E_{jk} + E_{kj}$ ($B = E_{jj}$ on the diagonal), and then
+/// the same for $C$.
+///
+pub fn gen_sp(n: usize, normalization: Normalization) -> Vec<nd::Array2<Complex64>> {
+    let mut basis = Vec::with_capacity(n * (2 * n + 1));
+    for j in 0..n {
+        for k in 0..n {
+            basis.push(matrix(2 * n, &[(j, k, 1.), (n + k, n + j, -1.)]));
+        }
+    }
+    for (row, column) in [(0, n), (n, 0)].iter() {
+        for j in 0..n {
+            for k in j..n {
+                let x = if j == k {
+                    matrix(2 * n, &[(row + j, column + j, 1.)])
+                } else {
+                    matrix(
+                        2 * n,
+                        &[(row + j, column + k, 1.), (row + k, column + j, 1.)],
+                    )
+                };
+                basis.push(x);
+            }
+        }
+    }
+    normalize(basis, normalization)
+}
+
+///
+/// Returns a basis of u(n), the anti-Hermitian `gen_gellmann` basis of su(n) followed by $i I$.
+/// With `Normalization::Unit` the matrices are not rescaled, so that $\mathrm{Tr}(T_a^\dagger
+/// T_a)$ is 2 for su(n) and $n$ for the identity. The basis is empty for $n = 0$.
+///
+pub fn gen_u(n: usize, normalization: Normalization) -> Vec<nd::Array2<Complex64>> {
+    let mut basis = crate::gellmann::gen_gellmann(n);
+    if n > 0 {
+        basis.push(nd::Array2::eye(n) * Complex64::new(0., 1.));
+    }
+    normalize(basis, normalization)
+}
@@ -0,0 +1,478 @@
+use ndarray as nd;
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::adjoint::ad_matrices;
+use crate::lie_algebra::{LieAlgebra, StructureConstants};
+use crate::roots::{root_system, RootSystem};
+
+///
+/// The type of a simple Lie algebra, e.g. $A_2$ or $E_8$
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CartanType {
+    series: char,
+    rank: usize,
+}
+
+impl CartanType {
+    /// The simple Lie algebra of a series and rank. The low rank coincidences are mapped to a
+    /// single type: $B_1 = C_1 = A_1$, $C_2 = B_2$ and $D_3 = A_3$. Returns an error if there is
+    /// no simple Lie algebra of this type, e.g. for $D_2$ or $E_3$.
+    pub fn new(series: char, rank: usize) -> Result<Self, String> {
+        canonical(series, rank)
+    }
+
+    /// One of 'A', 'B', 'C', 'D', 'E', 'F', 'G'
+    pub fn series(&self) -> char {
+        self.series
+    }
+
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+
+    /// The Cartan matrix $A_{ij} = 2 (\alpha_i, \alpha_j) / (\alpha_j, \alpha_j)$, with the
+    /// simple roots numbered as in Bourbaki
+    pub fn cartan_matrix(&self) -> nd::Array2<i32> {
+        let n = self.rank;
+        let mut a = nd::Array2::eye(n) * 2;
+        let mut link = |i: usize, j: usize, a_ij: i32, a_ji: i32| {
+            a[[i, j]] = a_ij;
+            a[[j, i]] = a_ji;
+        };
+
+        match self.series {
+            'A' | 'B' | 'C' => {
+                for i in 1..n {
+                    link(i - 1, i, -1, -1);
+                }
+                match self.series {
+                    'B' => link(n - 2, n - 1, -2, -1),
+                    'C' => link(n - 2, n - 1, -1, -2),
+                    _ => {}
+                }
+            }
+            'D' => {
+                for i in 1..n - 1 {
+                    link(i - 1, i, -1, -1);
+                }
+                link(n - 3, n - 1, -1, -1);
+            }
+            'E' => {
+                link(0, 2, -1, -1);
+                link(1, 3, -1, -1);
+                for i in 3..n {
+                    link(i - 1, i, -1, -1);
+                }
+            }
+            'F' => {
+                link(0, 1, -1, -1);
+                link(1, 2, -2, -1);
+                link(2, 3, -1, -1);
+            }
+            'G' => link(0, 1, -1, -3),
+            _ => unreachable!(),
+        }
+        a
+    }
+}
+
+// The type of a simple Lie algebra, mapping the low rank coincidences A1 = B1 = C1, B2 = C2 and
+// A3 = D3 to the first of them, or the reason why there is none
+fn canonical(series: char, rank: usize) -> Result<CartanType, String> {
+    let cartan_type = |series: char, rank: usize| Ok(CartanType { series, rank });
+    match (series, rank) {
+        ('A'..='D', 0) => Err(format!("{}0 is the zero algebra, not a simple one", series)),
+        ('A', _) => cartan_type('A', rank),
+        ('B', 1) | ('C', 1) => cartan_type('A', 1),
+        ('B', _) => cartan_type('B', rank),
+        ('C', 2) => cartan_type('B', 2),
+        ('C', _) => cartan_type('C', rank),
+        ('D', 1) => Err("D1 = so(2) is abelian, not simple".to_string()),
+        ('D', 2) => Err("D2 = A1 + A1 is semisimple but not simple".to_string()),
+        ('D', 3) => cartan_type('A', 3),
+        ('D', _) => cartan_type('D', rank),
+        ('E', 6..=8) | ('F', 4) | ('G', 2) => cartan_type(series, rank),
+        ('E'..='G', _) => Err(format!(
+            "There is no {}{}, the exceptional types are E6, E7, E8, F4 and G2",
+            series, rank
+        )),
+        _ => Err(format!("{} is not a series between A and G", series)),
+    }
+}
+
+impl fmt::Display for CartanType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.series, self.rank)
+    }
+}
+
+impl FromStr for CartanType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let mut chars = s.chars();
+        let series = chars
+            .next()
+            .ok_or_else(|| "Empty Cartan type".to_string())?
+            .to_ascii_uppercase();
+        let rank: usize = chars
+            .as_str()
+            .parse()
+            .map_err(|_| format!("{} is not a Cartan type", s))?;
+
+        canonical(series, rank)
+    }
+}
+
+///
+/// A simple ideal of a semisimple Lie algebra
+///
+#[derive(Clone, Debug)]
+pub struct SimpleComponent {
+    pub cartan_type: CartanType,
+    /// Indices of the simple roots of the component, in the order of its Dynkin diagram
+    pub nodes: Vec<usize>,
+    /// Text rendering of the Dynkin diagram, with nodes labelled by their simple root (from 1)
+    pub dynkin: String,
+}
+
+///
+/// The result of [`classify`]
+///
+#[derive(Clone, Debug)]
+pub struct Classification {
+    /// $A_{ij} = 2 (\alpha_i, \alpha_j) / (\alpha_j, \alpha_j)$
+    pub cartan_matrix: nd::Array2<i32>,
+    /// The simple components, by decreasing rank
+    pub components: Vec<SimpleComponent>,
+}
+
+impl Classification {
+    /// The Cartan type of the algebra, e.g. "B3 + A1"
+    pub fn cartan_type(&self) -> String {
+        let names: Vec<String> = self
+            .components
+            .iter()
+            .map(|x| x.cartan_type.to_string())
+            .collect();
+        names.join(" + ")
+    }
+
+    /// The Dynkin diagrams of all components, separated by blank lines
+    pub fn dynkin_diagram(&self) -> String {
+        let diagrams: Vec<&str> = self.components.iter().map(|x| x.dynkin.as_str()).collect();
+        diagrams.join("\n\n")
+    }
+}
+
+impl fmt::Display for Classification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\n{}", self.cartan_type(), self.dynkin_diagram())
+    }
+}
+
+///
+/// Classifies a semisimple Lie algebra through its root system. See
+/// [`classify_structure_constants`].
+///
+pub fn classify(algebra: &LieAlgebra) -> Classification {
+    classify_structure_constants(algebra.structure_constants())
+}
+
+///
+/// Classifies a semisimple Lie algebra from its structure constants alone, e.g. the output of
+/// `find_structure_constants`. The root system is found in the adjoint representation, which is
+/// faithful for a semisimple algebra and has the same structure constants.
+///
+pub fn classify_structure_constants(f: &StructureConstants) -> Classification {
+    let adjoint = LieAlgebra::from_structure_constants(ad_matrices(f), f.clone());
+    classify_root_system(&root_system(&adjoint))
+}
+
+///
+/// Classifies a root system through the Cartan matrix of its simple roots
+///
+pub fn classify_root_system(roots: &RootSystem) -> Classification {
+    let r = roots.rank();
+    let cartan_matrix = nd::Array2::from_shape_fn((r, r), |(i, j)| {
+        let x = roots.inner_product(&roots.simple_roots[i], &roots.coroots[j]);
+        assert!(
+            (x - x.round()).abs() < 1e-6,
+            "The Cartan matrix is not integral"
+        );
+        x.round() as i32
+    });
+
+    classify_cartan_matrix(&cartan_matrix)
+}
+
+///
+/// Splits a Cartan matrix into the connected components of its Dynkin diagram and finds the
+/// type of each one
+///
+pub fn classify_cartan_matrix(cartan_matrix: &nd::Array2<i32>) -> Classification {
+    let r = cartan_matrix.shape()[0];
+
+    let mut component_of: Vec<Option<usize>> = vec![None; r];
+    let mut components = Vec::new();
+    for start in 0..r {
+        if component_of[start].is_some() {
+            continue;
+        }
+        let mut nodes = vec![start];
+        component_of[start] = Some(components.len());
+        let mut i = 0;
+        while i < nodes.len() {
+            for j in 0..r {
+                if cartan_matrix[[nodes[i], j]] != 0 && component_of[j].is_none() {
+                    component_of[j] = Some(components.len());
+                    nodes.push(j);
+                }
+            }
+            i += 1;
+        }
+        nodes.sort_unstable();
+        components.push(simple_component(cartan_matrix, &nodes));
+    }
+
+    components.sort_by(|x: &SimpleComponent, y: &SimpleComponent| {
+        let x = x.cartan_type;
+        let y = y.cartan_type;
+        y.rank.cmp(&x.rank).then(x.series.cmp(&y.series))
+    });
+
+    Classification {
+        cartan_matrix: cartan_matrix.clone(),
+        components,
+    }
+}
+
+fn simple_component(a: &nd::Array2<i32>, nodes: &[usize]) -> SimpleComponent {
+    let n = nodes.len();
+    let bond = |i: usize, j: usize| a[[i, j]] * a[[j, i]];
+    let neighbours = |i: usize| -> Vec<usize> {
+        nodes
+            .iter()
+            .cloned()
+            .filter(|j| *j != i && a[[i, *j]] != 0)
+            .collect()
+    };
+    // The root i is longer than j
+    let is_longer = |i: usize, j: usize| a[[i, j]].abs() > a[[j, i]].abs();
+
+    let edges: usize = nodes.iter().map(|i| neighbours(*i).len()).sum::<usize>() / 2;
+    assert_eq!(edges + 1, n, "The Cartan matrix is not of finite type");
+
+    let max_bond = nodes
+        .iter()
+        .flat_map(|i| nodes.iter().map(move |j| (*i, *j)))
+        .filter(|(i, j)| i != j)
+        .map(|(i, j)| bond(i, j))
+        .max()
+        .unwrap_or(0);
+    let branch = nodes.iter().cloned().find(|i| neighbours(*i).len() > 2);
+
+    // Walk a chain from one of its ends
+    let chain = |start: usize| -> Vec<usize> {
+        let mut chain = vec![start];
+        let mut previous = None;
+        let mut current = start;
+        while let Some(next) = neighbours(current)
+            .into_iter()
+            .find(|j| Some(*j) != previous)
+        {
+            chain.push(next);
+            previous = Some(current);
+            current = next;
+        }
+        chain
+    };
+    let ends: Vec<usize> = nodes
+        .iter()
+        .cloned()
+        .filter(|i| neighbours(*i).len() <= 1)
+        .collect();
+
+    if let Some(branch) = branch {
+        assert_eq!(max_bond, 1, "The Cartan matrix is not of finite type");
+
+        // Arms from the branch node, by increasing length and with the lowest label on the left
+        // of the main chain
+        let mut arms: Vec<Vec<usize>> = neighbours(branch)
+            .into_iter()
+            .map(|first| {
+                let mut arm = vec![first];
+                let mut previous = branch;
+                let mut current = first;
+                while let Some(next) = neighbours(current).into_iter().find(|j| *j != previous) {
+                    arm.push(next);
+                    previous = current;
+                    current = next;
+                }
+                arm
+            })
+            .collect();
+        assert_eq!(arms.len(), 3, "The Cartan matrix is not of finite type");
+        arms.sort_by_key(|x| (x.len(), std::cmp::Reverse(x[x.len() - 1])));
+        let lengths: Vec<usize> = arms.iter().map(|x| x.len()).collect();
+
+        let cartan_type = match lengths.as_slice() {
+            [1, 1, k] => CartanType {
+                series: 'D',
+                rank: k + 3,
+            },
+            [1, 2, 2] => CartanType {
+                series: 'E',
+                rank: 6,
+            },
+            [1, 2, 3] => CartanType {
+                series: 'E',
+                rank: 7,
+            },
+            [1, 2, 4] => CartanType {
+                series: 'E',
+                rank: 8,
+            },
+            _ => panic!("The Cartan matrix is not of finite type"),
+        };
+
+        // The longest arm, the branch and the middle arm form the main chain, and the shortest
+        // arm hangs below the branch
+        let mut main: Vec<usize> = arms[2].iter().rev().cloned().collect();
+        main.push(branch);
+        main.extend(arms[1].iter());
+
+        let (line, column) = render_chain(&main, branch, &bond, &is_longer);
+        let dynkin = format!(
+            "{}\n{}|\n{}{}",
+            line,
+            " ".repeat(column),
+            " ".repeat(column),
+            arms[0][0] + 1
+        );
+
+        let mut nodes = main;
+        nodes.push(arms[0][0]);
+        return SimpleComponent {
+            cartan_type,
+            nodes,
+            dynkin,
+        };
+    }
+
+    let (cartan_type, order) = if n == 1 {
+        (
+            CartanType {
+                series: 'A',
+                rank: 1,
+            },
+            vec![nodes[0]],
+        )
+    } else {
+        let from_first = chain(ends[0]);
+        let from_last = chain(ends[1]);
+        match max_bond {
+            1 => (
+                CartanType {
+                    series: 'A',
+                    rank: n,
+                },
+                from_first,
+            ),
+            3 => {
+                assert_eq!(n, 2, "The Cartan matrix is not of finite type");
+                let order = if is_longer(ends[0], ends[1]) {
+                    from_first
+                } else {
+                    from_last
+                };
+                (
+                    CartanType {
+                        series: 'G',
+                        rank: 2,
+                    },
+                    order,
+                )
+            }
+            2 => {
+                let k = (0..n - 1)
+                    .find(|k| bond(from_first[*k], from_first[k + 1]) == 2)
+                    .unwrap();
+                let long_first = is_longer(from_first[k], from_first[k + 1]);
+
+                if n == 2 {
+                    let order = if long_first { from_first } else { from_last };
+                    (
+                        CartanType {
+                            series: 'B',
+                            rank: 2,
+                        },
+                        order,
+                    )
+                } else if k == 0 || k == n - 2 {
+                    // Put the double bond on the right. B_n has a single short root at the end,
+                    // C_n a single long one
+                    let (order, end_is_short) = if k == n - 2 {
+                        (from_first, long_first)
+                    } else {
+                        (from_last, !long_first)
+                    };
+                    let series = if end_is_short { 'B' } else { 'C' };
+                    (CartanType { series, rank: n }, order)
+                } else {
+                    assert_eq!(n, 4, "The Cartan matrix is not of finite type");
+                    // F4, with the long roots on the left
+                    let order = if long_first { from_first } else { from_last };
+                    (
+                        CartanType {
+                            series: 'F',
+                            rank: 4,
+                        },
+                        order,
+                    )
+                }
+            }
+            _ => panic!("The Cartan matrix is not of finite type"),
+        }
+    };
+
+    let (dynkin, _) = render_chain(&order, order[0], &bond, &is_longer);
+    SimpleComponent {
+        cartan_type,
+        nodes: order,
+        dynkin,
+    }
+}
+
+// Renders a chain of nodes such as "1---2=>=3", with arrows pointing to the shorter root.
+// Returns the text and the column of `marked`
+fn render_chain<B, L>(chain: &[usize], marked: usize, bond: &B, is_longer: &L) -> (String, usize)
+where
+    B: Fn(usize, usize) -> i32,
+    L: Fn(usize, usize) -> bool,
+{
+    let mut line = String::new();
+    let mut column = 0;
+    for (k, node) in chain.iter().enumerate() {
+        if k > 0 {
+            let previous = chain[k - 1];
+            let link = match (bond(previous, *node), is_longer(previous, *node)) {
+                (1, _) => "---",
+                (2, true) => "=>=",
+                (2, false) => "=<=",
+                (_, true) => "≡>≡",
+                (_, false) => "≡<≡",
+            };
+            line.push_str(link);
+        }
+        if *node == marked {
+            column = line.chars().count();
+        }
+        line.push_str(&(node + 1).to_string());
+    }
+    (line, column)
+}
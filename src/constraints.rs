@@ -0,0 +1,141 @@
+use ndarray as nd;
+
+use ndarray_linalg::*;
+
+///
+/// A linear condition on the elements $X$ of a matrix Lie algebra
+///
+#[derive(Clone, Debug)]
+pub enum Constraint {
+    /// $X^T M + M X = 0$, e.g. so(n) for $M = I$ or sp(2n) for the symplectic form
+    Bilinear(nd::Array2<c64>),
+    /// $X^\dagger M + M X = 0$, e.g. u(n) for $M = I$ or u(p, q) for $M = \eta$
+    Sesquilinear(nd::Array2<c64>),
+    /// $[X, A] = 0$, to restrict to the symmetries of an operator
+    Commutes(nd::Array2<c64>),
+    /// $\mathrm{Tr}(X) = 0$
+    Traceless,
+    /// $X = \bar{X}$, to keep only real matrices
+    Real,
+}
+
+impl Constraint {
+    // The residual of the constraint, which vanishes on the solutions
+    fn residual(&self, x: &nd::Array2<c64>) -> Vec<c64> {
+        match self {
+            Constraint::Bilinear(m) => (x.t().dot(m) + m.dot(x)).into_raw_vec(),
+            Constraint::Sesquilinear(m) => {
+                (x.t().mapv(|x| x.conj()).dot(m) + m.dot(x)).into_raw_vec()
+            }
+            Constraint::Commutes(a) => (x.dot(a) - a.dot(x)).into_raw_vec(),
+            Constraint::Traceless => vec![x.diag().sum()],
+            Constraint::Real => x.iter().map(|x| c64::new(0., x.im)).collect(),
+        }
+    }
+}
+
+///
+/// Computes a basis of the $n \times n$ matrices that satisfy every constraint.
+///
+/// The solutions form a real vector space: the conditions such as $X^\dagger M + M X = 0$ are
+/// only linear over the reals, so $X$ and $iX$ are independent elements whenever both are
+/// solutions. Each matrix is parametrized by the real and imaginary parts of its entries, and
+/// the solution space is the numerical nullspace of the constraints in these $2 n^2$
+/// parameters.
+///
+/// The nullspace is returned in reduced row echelon form, with the real parts of the entries in
+/// row-major order before the imaginary parts. This gives a standard basis: for example the real
+/// antisymmetric matrices give the $E_{jk} - E_{kj}$ of `gen_so`.
+///
+pub fn algebra_from_constraints(n: usize, constraints: &[Constraint]) -> Vec<nd::Array2<c64>> {
+    let params = 2 * n * n;
+    let unit = |k: usize| {
+        let mut x = nd::Array2::zeros((n, n));
+        let value = if k < n * n {
+            c64::new(1., 0.)
+        } else {
+            c64::new(0., 1.)
+        };
+        x[[(k % (n * n)) / n, k % n]] = value;
+        x
+    };
+
+    // One column per parameter, with the real and imaginary parts of every residual
+    let columns: Vec<Vec<f64>> = (0..params)
+        .map(|k| {
+            let x = unit(k);
+            constraints
+                .iter()
+                .flat_map(|c| c.residual(&x))
+                .flat_map(|r| vec![r.re, r.im])
+                .collect()
+        })
+        .collect();
+    let rows = columns.first().map_or(0, |x| x.len());
+
+    let null_space: Vec<nd::Array1<f64>> = if rows == 0 {
+        (0..params)
+            .map(|k| {
+                let mut x = nd::Array1::zeros(params);
+                x[k] = 1.;
+                x
+            })
+            .collect()
+    } else {
+        let a = nd::Array2::from_shape_fn((rows, params), |(i, k)| columns[k][i]);
+        let (_, s, vt) = a.svd(false, true).unwrap();
+        let vt = vt.unwrap();
+        let s_max = s.iter().cloned().fold(0., f64::max);
+        (0..params)
+            .filter(|i| *i >= s.len() || s[*i] <= 1e-10 * s_max.max(1.))
+            .map(|i| vt.row(i).to_owned())
+            .collect()
+    };
+
+    row_echelon(null_space)
+        .iter()
+        .map(|v| {
+            nd::Array2::from_shape_fn((n, n), |(j, k)| {
+                c64::new(v[j * n + k], v[n * n + j * n + k])
+            })
+        })
+        .collect()
+}
+
+// The reduced row echelon form of a set of independent vectors, with leading entries of 1
+fn row_echelon(mut rows: Vec<nd::Array1<f64>>) -> Vec<nd::Array1<f64>> {
+    let len = rows.first().map_or(0, |x| x.len());
+    let mut pivot_row = 0;
+    for col in 0..len {
+        if pivot_row == rows.len() {
+            break;
+        }
+        let best = (pivot_row..rows.len())
+            .max_by(|i, j| {
+                rows[*i][col]
+                    .abs()
+                    .partial_cmp(&rows[*j][col].abs())
+                    .unwrap()
+            })
+            .unwrap();
+        let value = rows[best][col];
+        if value.abs() < 1e-8 {
+            continue;
+        }
+        rows.swap(pivot_row, best);
+        let pivot = &rows[pivot_row] / value;
+        for (i, row) in rows.iter_mut().enumerate() {
+            if i != pivot_row {
+                let factor = row[col];
+                row.scaled_add(-factor, &pivot);
+            }
+        }
+        rows[pivot_row] = pivot;
+        pivot_row += 1;
+    }
+
+    for row in rows.iter_mut() {
+        row.mapv_inplace(|x| if x.abs() < 1e-10 { 0. } else { x });
+    }
+    rows
+}
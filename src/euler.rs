@@ -0,0 +1,330 @@
+use ndarray as nd;
+
+use ndarray_linalg::*;
+
+use std::f64::consts::PI;
+
+///
+/// The axes of the rotations of Euler angles, $e^{-i \alpha S_z} e^{-i \beta S_y}
+/// e^{-i \gamma S_z}$ for `Zyz` and $e^{-i \alpha S_z} e^{-i \beta S_x} e^{-i \gamma S_z}$ for `Zxz`
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EulerConvention {
+    Zyz,
+    Zxz,
+}
+
+///
+/// Euler angles of an element of SU(2), with $\alpha \in (-\pi, \pi]$, $\beta \in [0, \pi]$ and
+/// $\gamma \in (-2\pi, 2\pi]$, which cover SU(2) once
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EulerAngles {
+    pub alpha: f64,
+    pub beta: f64,
+    pub gamma: f64,
+    /// Whether $\beta$ is within [`GIMBAL_LOCK_TOLERANCE`] of 0 or $\pi$. Only $\alpha + \gamma$,
+    /// or $\alpha - \gamma$, is then determined, and $\alpha$ is set to 0.
+    pub gimbal_lock: bool,
+}
+
+///
+/// The distance of $\beta$ from 0 or $\pi$ below which the Euler angles are in gimbal lock
+///
+pub const GIMBAL_LOCK_TOLERANCE: f64 = 1e-9;
+
+///
+/// The element of SU(2) with the given Euler angles, in the spin 1/2 representation of
+/// `su2::gen_su2(0.5)`. For `Zyz`,
+/// $$e^{-i \alpha S_z} e^{-i \beta S_y} e^{-i \gamma S_z} = \begin{pmatrix}
+/// e^{-i (\alpha + \gamma) / 2} \cos \frac{\beta}{2} & -e^{-i (\alpha - \gamma) / 2} \sin
+/// \frac{\beta}{2} \\\\ e^{i (\alpha - \gamma) / 2} \sin \frac{\beta}{2} & e^{i (\alpha +
+/// \gamma) / 2} \cos \frac{\beta}{2} \end{pmatrix}$$
+///
+pub fn su2_from_euler(
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+    convention: EulerConvention,
+) -> nd::Array2<c64> {
+    // R_x(beta) = R_z(-pi / 2) R_y(beta) R_z(pi / 2)
+    let (alpha, gamma) = match convention {
+        EulerConvention::Zyz => (alpha, gamma),
+        EulerConvention::Zxz => (alpha - PI / 2., gamma + PI / 2.),
+    };
+    let (c, s) = ((beta / 2.).cos(), (beta / 2.).sin());
+    let sum = (alpha + gamma) / 2.;
+    let difference = (alpha - gamma) / 2.;
+    nd::arr2(&[
+        [c64::from_polar(c, -sum), c64::from_polar(-s, -difference)],
+        [c64::from_polar(s, difference), c64::from_polar(c, sum)],
+    ])
+}
+
+///
+/// The Euler angles of an element of SU(2) in the spin 1/2 representation.
+///
+/// Panics if the matrix is not in SU(2).
+///
+pub fn su2_to_euler(u: &nd::Array2<c64>, convention: EulerConvention) -> EulerAngles {
+    use approx::AbsDiffEq;
+
+    assert_eq!(u.shape(), &[2, 2], "The matrix is not 2 x 2");
+    let u_dagger = u.t().mapv(|x| x.conj());
+    assert!(
+        u_dagger.dot(u).abs_diff_eq(&nd::Array2::eye(2), 1e-8)
+            && u.det().unwrap().abs_diff_eq(&c64::new(1., 0.), 1e-8),
+        "The matrix is not in SU(2)"
+    );
+
+    let (a, b) = (u[[0, 0]], u[[1, 0]]);
+    let beta = 2. * b.norm().atan2(a.norm());
+    // (alpha + gamma) / 2 and (alpha - gamma) / 2
+    let sum = -a.arg();
+    let difference = b.arg();
+    let gimbal_lock = beta < GIMBAL_LOCK_TOLERANCE || PI - beta < GIMBAL_LOCK_TOLERANCE;
+    let (alpha, gamma) = if beta < GIMBAL_LOCK_TOLERANCE {
+        (0., 2. * sum)
+    } else if PI - beta < GIMBAL_LOCK_TOLERANCE {
+        (0., -2. * difference)
+    } else {
+        (sum + difference, sum - difference)
+    };
+
+    let (alpha, gamma) = match convention {
+        EulerConvention::Zyz => (alpha, gamma),
+        EulerConvention::Zxz => (alpha + PI / 2., gamma - PI / 2.),
+    };
+    let (alpha, gamma) = if !gimbal_lock {
+        (alpha, gamma)
+    } else if beta < GIMBAL_LOCK_TOLERANCE {
+        (0., alpha + gamma)
+    } else {
+        (0., gamma - alpha)
+    };
+
+    // Shifting both angles by 2 pi, or gamma by 4 pi, leaves the matrix unchanged
+    let shift = ((alpha - PI) / (2. * PI)).ceil();
+    let (alpha, gamma) = (alpha - 2. * PI * shift, gamma - 2. * PI * shift);
+    let gamma = gamma - 4. * PI * ((gamma - 2. * PI) / (4. * PI)).ceil();
+
+    EulerAngles {
+        alpha,
+        beta,
+        gamma,
+        gimbal_lock,
+    }
+}
+
+///
+/// The density of the Haar measure of SU(2) in Euler angles of either convention,
+/// $\frac{\sin \beta}{16 \pi^2}$, normalized on the ranges of [`EulerAngles`]
+///
+pub fn su2_euler_measure(beta: f64) -> f64 {
+    beta.sin() / (16. * PI * PI)
+}
+
+///
+/// The indices in `gen_gellmann(n)` of the generators of the generalized Euler angles of SU(n),
+/// in the order of the product.
+///
+/// Following Tilma and Sudarshan, "Generalized Euler angle parametrization for SU(N)",
+/// $$U = K_n K_{n - 1} \cdots K_2 \, e^{i \lambda_3 c_2} e^{i \lambda_8 c_3} \cdots
+/// e^{i \lambda_{n^2 - 1} c_n}, \quad K_m = \prod_{i = 2}^m e^{i \lambda_3 a_i}
+/// e^{i \lambda_{(i - 1)^2 + 1} b_i}$$
+/// where $\lambda_3 = D_1$ and $\lambda_{m^2 - 1} = D_{m - 1}$ are diagonal and
+/// $\lambda_{(i - 1)^2 + 1} = -i E_{1i} + i E_{i1}$. $K_m$ parametrizes the coset
+/// $SU(m) / S(U(m - 1) \times U(1))$, the complex projective space of the last column. For
+/// SU(3), this is the sequence $\lambda_3, \lambda_2, \lambda_3, \lambda_5, \lambda_3, \lambda_2,
+/// \lambda_3, \lambda_8$.
+///
+pub fn su_euler_generators(n: usize) -> Vec<usize> {
+    // The Gell-Mann matrix (j, k) is at (j - 1) n + (k - 1)
+    let index = |j: usize, k: usize| (j - 1) * n + (k - 1);
+    let mut res = vec![];
+    for m in (2..=n).rev() {
+        for i in 2..=m {
+            res.push(index(1, 1));
+            res.push(index(1, i));
+        }
+    }
+    for m in 2..=n {
+        res.push(index(m - 1, m - 1));
+    }
+    res
+}
+
+///
+/// The upper limits of the ranges $[0, x)$ of the generalized Euler angles of SU(n) that cover
+/// the group once: $\pi$ for the first $a_2$ of every $K_m$, $2 \pi$ for the other $a_i$,
+/// $\frac{\pi}{2}$ for the $b_i$ and $2 \pi \sqrt{\frac{m}{2 (m - 1)}}$ for $c_m$, e.g.
+/// $\sqrt{3} \pi$ for $c_3$.
+///
+pub fn su_euler_ranges(n: usize) -> Vec<f64> {
+    let mut res = vec![];
+    for m in (2..=n).rev() {
+        for i in 2..=m {
+            res.push(if i == 2 { PI } else { 2. * PI });
+            res.push(PI / 2.);
+        }
+    }
+    for m in 2..=n {
+        res.push(c_period(m));
+    }
+    res
+}
+
+// The period of c_m, for which exp(i c D_{m - 1}) is a central element of SU(m) times a phase
+fn c_period(m: usize) -> f64 {
+    2. * PI * (m as f64 / (2. * (m as f64 - 1.))).sqrt()
+}
+
+// The nonzero entries of D_{m - 1}, c on the first m - 1 entries and -(m - 1) c on the last
+fn d_norm(m: usize) -> f64 {
+    (2. / (m as f64 * (m as f64 - 1.))).sqrt()
+}
+
+///
+/// The element of SU(n) with the given generalized Euler angles, $\prod_k e^{\theta_k T_{g_k}}$
+/// for the generators $T$ of `gen_gellmann(n)` and the sequence $g$ of [`su_euler_generators`]
+///
+pub fn su_from_euler(n: usize, angles: &[f64]) -> nd::Array2<c64> {
+    let generators = su_euler_generators(n);
+    assert_eq!(
+        angles.len(),
+        generators.len(),
+        "SU(n) has n^2 - 1 Euler angles"
+    );
+    let mut res: nd::Array2<c64> = nd::Array2::eye(n);
+    for (g, theta) in generators.iter().zip(angles.iter()) {
+        res = res.dot(&exp_generator(*g, *theta, n));
+    }
+    res
+}
+
+// exp(theta T_g) in closed form, for the generators of the generalized Euler angles
+fn exp_generator(g: usize, theta: f64, n: usize) -> nd::Array2<c64> {
+    let (j, k) = (g / n + 1, g % n + 1);
+    let mut res: nd::Array2<c64> = nd::Array2::eye(n);
+    if j == k {
+        // exp(i theta D_j)
+        let c = d_norm(j + 1);
+        for p in 0..j {
+            res[[p, p]] = c64::from_polar(1., theta * c);
+        }
+        res[[j, j]] = c64::from_polar(1., -theta * c * j as f64);
+    } else {
+        // exp(theta (E_jk - E_kj)), for j < k
+        let (cos, sin) = (theta.cos(), theta.sin());
+        res[[j - 1, j - 1]] = c64::new(cos, 0.);
+        res[[j - 1, k - 1]] = c64::new(sin, 0.);
+        res[[k - 1, j - 1]] = c64::new(-sin, 0.);
+        res[[k - 1, k - 1]] = c64::new(cos, 0.);
+    }
+    res
+}
+
+///
+/// The generalized Euler angles of an element of SU(n), in the ranges of [`su_euler_ranges`].
+///
+/// The factors are peeled off from the left: at each level m, the $a_i$ and $b_i$ of $K_m$ bring
+/// the last column of the top-left $m \times m$ block to a phase times $e_m$, one entry at a
+/// time, and $c_m$ removes the phase. Angles that are not determined, when an entry of the column
+/// vanishes, are set to 0.
+///
+/// Panics if the matrix is not in SU(n).
+///
+pub fn su_to_euler(u: &nd::Array2<c64>) -> Vec<f64> {
+    use approx::AbsDiffEq;
+
+    let n = u.shape()[0];
+    let u_dagger = u.t().mapv(|x| x.conj());
+    assert!(
+        u_dagger.dot(u).abs_diff_eq(&nd::Array2::eye(n), 1e-8)
+            && u.det().unwrap().abs_diff_eq(&c64::new(1., 0.), 1e-8),
+        "The matrix is not in SU(n)"
+    );
+
+    let wrap = |x: f64, period: f64| x - period * (x / period).floor();
+    let mut block = u.clone();
+    let mut angles = vec![];
+    let mut phases = vec![];
+    for m in (2..=n).rev() {
+        for i in 2..=m {
+            let (x0, x) = (block[[0, m - 1]], block[[i - 1, m - 1]]);
+            // The ratio x / x0 has to be brought to a negative real, or positive for the last one.
+            // D_1 acts on both x0 and x for i = 2.
+            let factor = if i == 2 { 2. } else { 1. };
+            let target = if i < m { PI } else { 0. };
+            let a = if x0.norm() < 1e-12 || x.norm() < 1e-12 {
+                0.
+            } else {
+                wrap((target - x.arg() + x0.arg()) / factor, 2. * PI / factor)
+            };
+            let b = if i < m {
+                x.norm().atan2(x0.norm())
+            } else {
+                x0.norm().atan2(x.norm())
+            };
+
+            // block = R_{1i}(-b) e^{-i a D_1} block
+            let (phase0, phase1) = (c64::from_polar(1., -a), c64::from_polar(1., a));
+            for col in 0..m {
+                block[[0, col]] *= phase0;
+                block[[1, col]] *= phase1;
+                let (y0, y) = (block[[0, col]], block[[i - 1, col]]);
+                block[[0, col]] = y0 * b.cos() - y * b.sin();
+                block[[i - 1, col]] = y0 * b.sin() + y * b.cos();
+            }
+            angles.push(a);
+            angles.push(b);
+        }
+
+        // The last entry is exp(-i c (m - 1) |D|), and the rest of the block is in U(m - 1)
+        let c = wrap(
+            -block[[m - 1, m - 1]].arg() / ((m - 1) as f64 * d_norm(m)),
+            c_period(m),
+        );
+        let phase = c64::from_polar(1., -c * d_norm(m));
+        block = block.slice(nd::s![..m - 1, ..m - 1]).mapv(|x| x * phase);
+        phases.push(c);
+    }
+    phases.reverse();
+    angles.extend(phases);
+    angles
+}
+
+///
+/// The density of the Haar measure of SU(n) in generalized Euler angles, normalized on the
+/// ranges of [`su_euler_ranges`].
+///
+/// The measure factorizes over the cosets: $K_m$ contributes the Fubini–Study measure of
+/// $\mathbb{CP}^{m - 1}$,
+/// $$\cos b_m \sin^{2m - 3} b_m \prod_{i = 2}^{m - 1} \sin b_i \cos^{2i - 3} b_i$$
+/// while the $a_i$ and $c_m$ contribute a constant.
+///
+pub fn su_euler_measure(n: usize, angles: &[f64]) -> f64 {
+    assert_eq!(angles.len(), n * n - 1, "SU(n) has n^2 - 1 Euler angles");
+    let mut density = 1.;
+    let mut offset = 0;
+    for m in (2..=n).rev() {
+        for i in 2..=m {
+            let b = angles[offset + 2 * (i - 2) + 1];
+            // Each weight integrates to 1 / (2i - 2) over [0, pi / 2]
+            let weight = if i == m {
+                b.cos() * b.sin().powi(2 * i as i32 - 3)
+            } else {
+                b.sin() * b.cos().powi(2 * i as i32 - 3)
+            };
+            density *= weight * (2 * i - 2) as f64;
+        }
+        offset += 2 * (m - 1);
+    }
+    let volume: f64 = su_euler_ranges(n)
+        .iter()
+        .zip(su_euler_generators(n).iter())
+        .filter(|(_, g)| **g % (n + 1) == 0)
+        .map(|(x, _)| x)
+        .product();
+    density / volume
+}
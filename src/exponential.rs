@@ -0,0 +1,405 @@
+use ndarray as nd;
+use ndarray::ShapeBuilder;
+
+use ndarray_linalg::*;
+
+use rayon::prelude::*;
+
+use crate::lie_algebra::LieAlgebra;
+use crate::su2;
+
+///
+/// The group element $\exp(\sum_a c_a T_a)$ for the coordinates $c$ of an element of the
+/// algebra. For real coordinates in an anti-Hermitian basis such as `gen_gellmann`, the result
+/// is in SU(n).
+///
+/// The exponential is evaluated in closed form whenever possible:
+/// - for 2 x 2 matrices, $\exp(X) = \cosh(s) I + \frac{\sinh(s)}{s} X$ with $s^2 = -\det X$
+///   (after removing the trace);
+/// - for an element $X = -i \vec{a} \cdot \vec{S}$ of a spin j representation of su(2), such as
+///   the span of `su2::gen_su2(j)`, by the Rodrigues formula
+///   $$\exp(-i \vec{a} \cdot \vec{S}) = \sum_{m=-j}^{j} e^{-i m \kappa} \prod_{m' \neq m}
+///   \frac{\vec{a} \cdot \vec{S} - m' \kappa}{(m - m') \kappa}, \quad \kappa^2 = \vec{a} \cdot
+///   \vec{a}$$
+///   This formula divides by differences of eigenvalues and loses precision as j grows or as
+///   $\kappa$ shrinks, so it is only used up to j = 4, i.e. 9 x 9 matrices, and for $|\kappa|
+///   \geq 0.1 |\vec{a}|$, which excludes the elements close to a nilpotent one. The others take
+///   one of the paths below;
+/// - for an anti-Hermitian $X$, through the eigendecomposition of the Hermitian matrix $iX$.
+///
+/// Any other element falls back to [`expm`].
+///
+pub fn exp_map(algebra: &LieAlgebra, coords: &nd::Array1<c64>) -> nd::Array2<c64> {
+    assert_eq!(
+        coords.len(),
+        algebra.dim(),
+        "The coordinates do not match the dimension of the algebra"
+    );
+    exp_element(&algebra.from_coordinates(coords))
+}
+
+///
+/// [`exp_map`] for every row of `coords`, evaluated in parallel
+///
+pub fn exp_map_batch(algebra: &LieAlgebra, coords: &nd::Array2<c64>) -> Vec<nd::Array2<c64>> {
+    let rows: Vec<nd::Array1<c64>> = coords.genrows().into_iter().map(|x| x.to_owned()).collect();
+    rows.par_iter().map(|x| exp_map(algebra, x)).collect()
+}
+
+// The exponential of a matrix, through the fastest applicable method
+fn exp_element(x: &nd::Array2<c64>) -> nd::Array2<c64> {
+    let n = x.shape()[0];
+    if n == 1 {
+        return x.mapv(|x| x.exp());
+    }
+    if n == 2 {
+        return exp_2x2(x);
+    }
+    if n <= MAX_SPIN_DIM {
+        if let Some(u) = exp_spin(x) {
+            return u;
+        }
+    }
+    if is_anti_hermitian(x) {
+        return exp_anti_hermitian(x);
+    }
+    expm(x)
+}
+
+// The Rodrigues formula divides by differences of eigenvalues, which loses precision for large
+// spins. Keep in sync with the documentation of `exp_map`.
+const MAX_SPIN_DIM: usize = 9;
+
+fn is_anti_hermitian(x: &nd::Array2<c64>) -> bool {
+    use approx::AbsDiffEq;
+
+    let scale = x.iter().map(|x| x.norm()).fold(1., f64::max);
+    x.abs_diff_eq(&x.t().mapv(|x| -x.conj()), 1e-12 * scale)
+}
+
+fn exp_2x2(x: &nd::Array2<c64>) -> nd::Array2<c64> {
+    let half_trace = (x[[0, 0]] + x[[1, 1]]) / 2.;
+    let identity: nd::Array2<c64> = nd::Array2::eye(2);
+    let x = x - &(&identity * half_trace);
+
+    // X^2 = s^2 I for a traceless 2 x 2 matrix
+    let s = (-(x[[0, 0]] * x[[1, 1]] - x[[0, 1]] * x[[1, 0]])).sqrt();
+    let sinh_s = if s.norm() < 1e-4 {
+        // sinh(s) / s = 1 + s^2 / 6 + s^4 / 120 + ...
+        let s2 = s * s;
+        c64::new(1., 0.) + s2 / 6. + s2 * s2 / 120.
+    } else {
+        s.sinh() / s
+    };
+
+    (identity * s.cosh() + x * sinh_s) * half_trace.exp()
+}
+
+// exp(-i a.S) for a spin j element, or None if x is not one
+fn exp_spin(x: &nd::Array2<c64>) -> Option<nd::Array2<c64>> {
+    use approx::AbsDiffEq;
+
+    let n = x.shape()[0];
+    let j = (n as f64 - 1.) / 2.;
+    let to_complex = |m: nd::Array2<f64>| m.mapv(|x| c64::new(x, 0.));
+    let spin = [
+        to_complex(su2::s_x(j)),
+        to_complex(su2::s_y(j)) * c64::new(0., -1.),
+        to_complex(su2::s_z(j)),
+    ];
+
+    // a_k = i Tr(X S_k) / Tr(S_k^2)
+    let norm = j * (j + 1.) * (2. * j + 1.) / 3.;
+    let a: Vec<c64> = spin
+        .iter()
+        .map(|s| c64::new(0., 1.) * (x * &s.t()).sum() / norm)
+        .collect();
+
+    let mut a_s: nd::Array2<c64> = nd::Array2::zeros((n, n));
+    for (a_k, s) in a.iter().zip(spin.iter()) {
+        a_s.scaled_add(*a_k, s);
+    }
+    let scale = x.iter().map(|x| x.norm()).fold(1., f64::max);
+    if !(&a_s * c64::new(0., -1.)).abs_diff_eq(x, 1e-12 * scale) {
+        return None;
+    }
+
+    // The eigenvalues of a.S are m kappa. For complex a, kappa can be much smaller than |a|
+    // near the nilpotent elements, where the product of 2j factors of size |a| / kappa
+    // loses all precision
+    let kappa = a.iter().map(|x| x * x).sum::<c64>().sqrt();
+    let a_norm = a.iter().map(|x| x.norm_sqr()).sum::<f64>().sqrt();
+    if kappa.norm() < 1e-6 || kappa.norm() < 0.1 * a_norm {
+        return None;
+    }
+
+    let identity: nd::Array2<c64> = nd::Array2::eye(n);
+    let ms: Vec<f64> = (0..n).map(|k| j - k as f64).collect();
+    let mut u = nd::Array2::zeros((n, n));
+    for m in ms.iter() {
+        let mut projector = identity.clone();
+        for m_other in ms.iter().filter(|x| *x != m) {
+            let factor = (&a_s - &(&identity * (kappa * *m_other))) / (kappa * (m - m_other));
+            projector = projector.dot(&factor);
+        }
+        u.scaled_add((c64::new(0., -m) * kappa).exp(), &projector);
+    }
+    Some(u)
+}
+
+fn exp_anti_hermitian(x: &nd::Array2<c64>) -> nd::Array2<c64> {
+    // X = -i H with H = iX Hermitian, so that exp(X) = V exp(-i lambda) V^dagger
+    // In Fortran layout, so that the columns of v are the eigenvectors of h and not of its
+    // conjugate
+    let mut h = nd::Array2::zeros(x.dim().f());
+    h.assign(&(x * c64::new(0., 1.)));
+    let (e, v) = h.eigh(UPLO::Upper).unwrap();
+    let phases = e.mapv(|x| c64::new(0., -x).exp());
+    let v_dagger = v.t().mapv(|x| x.conj());
+    (&v * &phases).dot(&v_dagger)
+}
+
+///
+/// The exponential of any square matrix, by scaling and squaring of its Taylor series
+///
+pub fn expm(x: &nd::Array2<c64>) -> nd::Array2<c64> {
+    let n = x.shape()[0];
+    let norm = x
+        .genrows()
+        .into_iter()
+        .map(|row| row.iter().map(|x| x.norm()).sum::<f64>())
+        .fold(0., f64::max);
+
+    // Scale so that the norm is below 1/2, where 18 terms are accurate to machine precision
+    let squarings = if norm > 0.5 {
+        (norm / 0.5).log2().ceil() as i32
+    } else {
+        0
+    };
+    let x = x * c64::new(0.5f64.powi(squarings), 0.);
+
+    let mut res: nd::Array2<c64> = nd::Array2::eye(n);
+    let mut term: nd::Array2<c64> = nd::Array2::eye(n);
+    for k in 1..=18 {
+        term = term.dot(&x) / c64::new(k as f64, 0.);
+        res += &term;
+    }
+    for _ in 0..squarings {
+        res = res.dot(&res);
+    }
+    res
+}
+
+///
+/// A logarithm of a group element, as coordinates in the basis of an algebra
+///
+#[derive(Clone, Debug)]
+pub struct Logarithm {
+    /// The coordinates $c$ with $\exp(\sum_a c_a T_a) = U$
+    pub coords: nd::Array1<c64>,
+    /// Whether an eigenvalue of $U$ is within [`BRANCH_CUT_TOLERANCE`] of $-1$, where the
+    /// principal logarithm jumps between $\pm i \pi$
+    pub branch_cut: bool,
+}
+
+///
+/// The angle from $-1$ below which an eigenvalue $e^{i \theta}$ is reported to be on the branch
+/// cut
+///
+pub const BRANCH_CUT_TOLERANCE: f64 = 1e-6;
+
+///
+/// The largest Frobenius norm of $U^\dagger U - I$ for which [`log_map`] accepts $U$ as unitary
+///
+pub const UNITARY_TOLERANCE: f64 = 1e-8;
+
+///
+/// The principal logarithm of a unitary matrix $U$, as coordinates in the basis of an algebra
+/// such as su(n) or u(n) in the defining representation: `gen_gellmann`, `gen_sylvester`,
+/// `hermitian_basis_from_spin`, ...
+///
+/// With $U = \sum_k e^{i \theta_k} |v_k\rangle \langle v_k|$ and $\theta_k \in (-\pi, \pi]$, the
+/// principal logarithm $\sum_k i \theta_k |v_k\rangle \langle v_k|$ is not traceless when
+/// $\sum_k \theta_k \neq 0$, even for $U \in SU(n)$. The result is then the logarithm of smallest
+/// norm in the algebra that shifts the largest angles by $-2\pi$ or the smallest by $2 \pi$.
+/// When several logarithms have this norm, for example for degenerate eigenvalues, the first
+/// one of [`all_logarithms`] is returned.
+///
+/// Returns an error if $U$ is not unitary to within [`UNITARY_TOLERANCE`] or if it has no such
+/// logarithm in the algebra.
+///
+pub fn log_map(algebra: &LieAlgebra, u: &nd::Array2<c64>) -> Result<Logarithm, String> {
+    let (angles, _) = unitary_eigh(u)?;
+    let branch_cut = angles
+        .iter()
+        .any(|x| std::f64::consts::PI - x.abs() < BRANCH_CUT_TOLERANCE);
+    let coords = all_logarithms(algebra, u, 1)?.swap_remove(0);
+    Ok(Logarithm { coords, branch_cut })
+}
+
+///
+/// Every logarithm of smallest norm of a unitary matrix in the algebra, as coordinates.
+///
+/// There are several of them when an eigenvalue is $-1$, which has the two logarithms
+/// $\pm i \pi$, or when a degenerate eigenvalue must be split between two branches to fit in
+/// the algebra, e.g. $-I \in SU(2)$ has the logarithms $\pm i \pi \sigma_z$. In the degenerate
+/// case the logarithms form continuous families, and one is returned for each way to assign the
+/// branches to the computed eigenvectors. Their number grows as a binomial coefficient, e.g.
+/// $\binom{n}{n/2}$ for $-I \in SU(n)$ with even $n$, so the search stops after `limit` of them.
+///
+/// Returns an error if $U$ is not unitary to within [`UNITARY_TOLERANCE`] or if it has no such
+/// logarithm in the algebra. Panics if `limit` is 0.
+///
+pub fn all_logarithms(
+    algebra: &LieAlgebra,
+    u: &nd::Array2<c64>,
+    limit: usize,
+) -> Result<Vec<nd::Array1<c64>>, String> {
+    use approx::AbsDiffEq;
+    use itertools::Itertools;
+
+    assert_eq!(
+        u.shape()[0],
+        algebra.matrix_dim(),
+        "The matrix does not match the representation of the algebra"
+    );
+    assert!(limit > 0, "At least one logarithm must be requested");
+    let (angles, v) = unitary_eigh(u)?;
+    let n = angles.len();
+    let two_pi = 2. * std::f64::consts::PI;
+
+    // The eigenvalues by decreasing angle, to shift the largest ones down and the smallest ones up
+    let order: Vec<usize> = (0..n)
+        .sorted_by(|a, b| angles[*b].partial_cmp(&angles[*a]).unwrap())
+        .collect();
+    let sorted: Vec<f64> = order.iter().map(|k| angles[*k]).collect();
+
+    // The candidates shift the `down` largest angles by -2 pi and the `up` smallest by 2 pi. Their
+    // norm only depends on these numbers, as tied angles are equal
+    let norm = |theta: &[f64]| theta.iter().map(|x| x * x).sum::<f64>();
+    let shifts: Vec<(usize, usize, f64)> = (0..=n)
+        .flat_map(|down| (0..=(n - down)).map(move |up| (down, up)))
+        .map(|(down, up)| {
+            let theta: Vec<f64> = (0..n)
+                .map(|i| match i {
+                    i if i < down => sorted[i] - two_pi,
+                    i if i >= n - up => sorted[i] + two_pi,
+                    i => sorted[i],
+                })
+                .collect();
+            (down, up, norm(&theta))
+        })
+        .sorted_by(|a, b| a.2.partial_cmp(&b.2).unwrap())
+        .collect();
+
+    let v_dagger = v.t().mapv(|x| x.conj());
+    let mut logarithms: Vec<nd::Array1<c64>> = vec![];
+    let mut min_norm = 0.;
+    'search: for (down, up, shift_norm) in shifts.iter() {
+        if !logarithms.is_empty() && *shift_norm > min_norm + 1e-8 {
+            break;
+        }
+        // Every subset of the eigenvalues tied at the boundaries is shifted in turn
+        let (start, end) = tie(&sorted, *down);
+        let (start_up, end_up) = tie(&sorted, n - up);
+        for shifted in (start..end).combinations(down - start) {
+            for shifted_up in (start_up..end_up).combinations(end_up - (n - up)) {
+                let mut theta = angles.clone();
+                for i in (0..start).chain(shifted.iter().cloned()) {
+                    theta[order[i]] -= two_pi;
+                }
+                for i in (end_up..n).chain(shifted_up.iter().cloned()) {
+                    theta[order[i]] += two_pi;
+                }
+                let phases: nd::Array1<c64> = theta.iter().map(|x| c64::new(0., *x)).collect();
+                let x = (&v * &phases).dot(&v_dagger);
+                let coords = algebra.to_coordinates(&x);
+                let duplicate = logarithms.iter().any(|y| y.abs_diff_eq(&coords, 1e-8));
+                if !duplicate && algebra.from_coordinates(&coords).abs_diff_eq(&x, 1e-8) {
+                    min_norm = *shift_norm;
+                    logarithms.push(coords);
+                    if logarithms.len() == limit {
+                        break 'search;
+                    }
+                }
+            }
+        }
+    }
+    if logarithms.is_empty() {
+        return Err("The matrix has no logarithm in the algebra".to_string());
+    }
+    Ok(logarithms)
+}
+
+// The distance below which two angles are one degenerate eigenvalue, whose eigenvectors can be
+// split between two branches of the logarithm
+const TIE_TOLERANCE: f64 = 1e-6;
+
+// The range of the sorted angles equal to the one at index k - 1 or k, which is split when only
+// some of them are shifted
+fn tie(sorted: &[f64], k: usize) -> (usize, usize) {
+    let n = sorted.len();
+    if k == 0 || k == n {
+        return (k, k);
+    }
+    if (sorted[k - 1] - sorted[k]).abs() > TIE_TOLERANCE {
+        return (k, k);
+    }
+    let value = sorted[k];
+    let start = (0..k)
+        .find(|i| (sorted[*i] - value).abs() <= TIE_TOLERANCE)
+        .unwrap();
+    let end = (k..n)
+        .find(|i| (sorted[*i] - value).abs() > TIE_TOLERANCE)
+        .unwrap_or(n);
+    (start, end)
+}
+
+// The angles theta in (-pi, pi] and orthonormal eigenvectors of a unitary matrix
+fn unitary_eigh(u: &nd::Array2<c64>) -> Result<(Vec<f64>, nd::Array2<c64>), String> {
+    use approx::AbsDiffEq;
+
+    let n = u.shape()[0];
+    let u_dagger = u.t().mapv(|x| x.conj());
+    let error = (u_dagger.dot(u) - nd::Array2::<c64>::eye(n))
+        .iter()
+        .map(|x| x.norm_sqr())
+        .sum::<f64>()
+        .sqrt();
+    if error.is_nan() || error > UNITARY_TOLERANCE {
+        return Err(format!(
+            "The matrix is not unitary: U^dagger U differs from I by {:e}",
+            error
+        ));
+    }
+
+    // A unitary matrix is normal, so that eigenvectors of distinct eigenvalues are orthogonal
+    // up to rounding, and those of a degenerate eigenvalue span its eigenspace. The QR
+    // decomposition makes them orthonormal without leaving the eigenspaces.
+    let (_, v) = u.eig().map_err(|e| e.to_string())?;
+    let (v, _) = v.qr().map_err(|e| e.to_string())?;
+
+    let eigenvalues: Vec<c64> = v
+        .gencolumns()
+        .into_iter()
+        .map(|x| x.mapv(|x| x.conj()).dot(&u.dot(&x)))
+        .collect();
+    let v_dagger = v.t().mapv(|x| x.conj());
+    let reconstructed = (&v * &nd::arr1(&eigenvalues)).dot(&v_dagger);
+    if !reconstructed.abs_diff_eq(u, UNITARY_TOLERANCE) {
+        return Err("The eigenvectors of the matrix could not be separated".to_string());
+    }
+    let angles = eigenvalues
+        .iter()
+        .map(|x| {
+            let theta = x.arg();
+            // Eigenvalues on the branch cut are taken at pi rather than -pi
+            if theta < BRANCH_CUT_TOLERANCE - std::f64::consts::PI {
+                theta + 2. * std::f64::consts::PI
+            } else {
+                theta
+            }
+        })
+        .collect();
+    Ok((angles, v))
+}
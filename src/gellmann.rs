@@ -1,6 +1,8 @@
 use ndarray as nd;
 use num_complex::Complex64;
 
+use crate::lie_algebra::StructureConstants;
+
 ///
 /// Construct an element for Generalized Gell-Mann Matrix
 /// https://en.wikipedia.org/wiki/Generalizations_of_Pauli_matrices
@@ -58,3 +60,166 @@ pub fn gen_gellmann(d: usize) -> Vec<nd::Array2<Complex64>> {
     }
     basis
 }
+
+// Position of the Gell-Mann matrix (j, k) in `gen_gellmann`, with 1-based j and k
+fn index(j: usize, k: usize, d: usize) -> usize {
+    (j - 1) * d + (k - 1)
+}
+
+// Symmetric matrix E_mn + E_nm, for m < n
+fn sym(m: usize, n: usize, d: usize) -> usize {
+    index(n, m, d)
+}
+
+// Antisymmetric matrix -i E_mn + i E_nm, for m < n
+fn antisym(m: usize, n: usize, d: usize) -> usize {
+    index(m, n, d)
+}
+
+// Diagonal matrix D_l, for 1 <= l < d
+fn diag(l: usize, d: usize) -> usize {
+    index(l, l, d)
+}
+
+// Entry p of the diagonal of D_l
+fn diag_entry(l: usize, p: usize) -> f64 {
+    let c = (2. / (l as f64 * (l as f64 + 1.))).sqrt();
+    if p <= l {
+        c
+    } else if p == l + 1 {
+        -(l as f64) * c
+    } else {
+        0.
+    }
+}
+
+// Calls `f` with every ordered triple of (a, b, c) and the sign of the permutation
+fn permutations<F>(a: usize, b: usize, c: usize, mut f: F)
+where
+    F: FnMut(usize, usize, usize, f64),
+{
+    f(a, b, c, 1.);
+    f(b, c, a, 1.);
+    f(c, a, b, 1.);
+    f(b, a, c, -1.);
+    f(a, c, b, -1.);
+    f(c, b, a, -1.);
+}
+
+///
+/// The structure constants of the basis from `gen_gellmann(d)`, from their closed form.
+///
+/// With the Hermitian matrices $\lambda_a$ satisfying $[\lambda_a, \lambda_b] = 2i \sum_c
+/// f^{\lambda}_{abc} \lambda_c$, the nonzero and totally antisymmetric $f^{\lambda}$ are, for $x <
+/// y < z$ and the diagonal $D_l$:
+///
+/// $$f^{\lambda}(S_{xy}, S_{xz}, A_{yz}) = f^{\lambda}(A_{xy}, S_{xz}, S_{yz}) =
+/// f^{\lambda}(A_{xy}, A_{xz}, A_{yz}) = -f^{\lambda}(S_{xy}, A_{xz}, S_{yz}) = \frac{1}{2}$$
+///
+/// $$f^{\lambda}(D_l, S_{mn}, A_{mn}) = \frac{(D_l)_{mm} - (D_l)_{nn}}{2}$$
+///
+/// The basis is $T_a = i \lambda_a$, so $f_{abc} = -2 f^{\lambda}_{abc}$. This agrees with
+/// `find_structure_constants(&gen_gellmann(d))`.
+///
+pub fn structure_constants(d: usize) -> StructureConstants {
+    let mut f = StructureConstants::new(d * d - 1);
+
+    let mut insert = |a, b, c, value: f64| {
+        permutations(a, b, c, |a, b, c, sign| {
+            f.insert(a, b, c, Complex64::new(-2. * sign * value, 0.))
+        })
+    };
+
+    for x in 1..d + 1 {
+        for y in x + 1..d + 1 {
+            for z in y + 1..d + 1 {
+                let (s_xy, s_xz, s_yz) = (sym(x, y, d), sym(x, z, d), sym(y, z, d));
+                let (a_xy, a_xz, a_yz) = (antisym(x, y, d), antisym(x, z, d), antisym(y, z, d));
+
+                insert(s_xy, s_xz, a_yz, 0.5);
+                insert(a_xy, s_xz, s_yz, 0.5);
+                insert(a_xy, a_xz, a_yz, 0.5);
+                insert(s_xy, a_xz, s_yz, -0.5);
+            }
+        }
+    }
+
+    for l in 1..d {
+        for m in 1..d + 1 {
+            for n in m + 1..d + 1 {
+                let value = (diag_entry(l, m) - diag_entry(l, n)) / 2.;
+                if value != 0. {
+                    insert(diag(l, d), sym(m, n, d), antisym(m, n, d), value);
+                }
+            }
+        }
+    }
+
+    f
+}
+
+///
+/// The d coefficients of the basis from `gen_gellmann(d)`, from their closed form.
+///
+/// With $\{\lambda_a, \lambda_b\} = \frac{4}{d} \delta_{ab} I + 2 \sum_c d^{\lambda}_{abc}
+/// \lambda_c$, the nonzero and totally symmetric $d^{\lambda}$ are, for $x < y < z$, $l < k$ and
+/// $m < n$:
+///
+/// $$d^{\lambda}(S_{xy}, S_{xz}, S_{yz}) = d^{\lambda}(S_{xy}, A_{xz}, A_{yz}) =
+/// d^{\lambda}(A_{xy}, A_{xz}, S_{yz}) = -d^{\lambda}(A_{xy}, S_{xz}, A_{yz}) = \frac{1}{2}$$
+///
+/// $$d^{\lambda}(D_l, S_{mn}, S_{mn}) = d^{\lambda}(D_l, A_{mn}, A_{mn}) = \frac{(D_l)_{mm} +
+/// (D_l)_{nn}}{2}$$
+///
+/// $$d^{\lambda}(D_l, D_l, D_k) = \sqrt{\frac{2}{k(k + 1)}}, \quad d^{\lambda}(D_l, D_l, D_l) =
+/// -\sqrt{\frac{2}{l(l + 1)}} (l - 1)$$
+///
+/// The basis is $T_a = i \lambda_a$, so $d_{abc} = 2i d^{\lambda}_{abc}$. This agrees with
+/// `find_d_coefficients(&gen_gellmann(d))`.
+///
+pub fn d_coefficients(d: usize) -> StructureConstants {
+    let mut d_abc = StructureConstants::new(d * d - 1);
+
+    let mut insert = |a, b, c, value: f64| {
+        permutations(a, b, c, |a, b, c, _| {
+            d_abc.insert(a, b, c, Complex64::new(0., 2. * value))
+        })
+    };
+
+    for x in 1..d + 1 {
+        for y in x + 1..d + 1 {
+            for z in y + 1..d + 1 {
+                let (s_xy, s_xz, s_yz) = (sym(x, y, d), sym(x, z, d), sym(y, z, d));
+                let (a_xy, a_xz, a_yz) = (antisym(x, y, d), antisym(x, z, d), antisym(y, z, d));
+
+                insert(s_xy, s_xz, s_yz, 0.5);
+                insert(s_xy, a_xz, a_yz, 0.5);
+                insert(a_xy, a_xz, s_yz, 0.5);
+                insert(a_xy, s_xz, a_yz, -0.5);
+            }
+        }
+    }
+
+    for l in 1..d {
+        for m in 1..d + 1 {
+            for n in m + 1..d + 1 {
+                let value = (diag_entry(l, m) + diag_entry(l, n)) / 2.;
+                if value != 0. {
+                    insert(diag(l, d), sym(m, n, d), sym(m, n, d), value);
+                    insert(diag(l, d), antisym(m, n, d), antisym(m, n, d), value);
+                }
+            }
+        }
+
+        for k in l + 1..d {
+            insert(diag(l, d), diag(l, d), diag(k, d), diag_entry(k, 1));
+        }
+
+        let value = -diag_entry(l, 1) * (l as f64 - 1.);
+        if value != 0. {
+            insert(diag(l, d), diag(l, d), diag(l, d), value);
+        }
+    }
+
+    d_abc
+}
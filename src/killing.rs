@@ -0,0 +1,144 @@
+use ndarray as nd;
+
+use ndarray_linalg::*;
+
+use std::collections::HashMap;
+
+use crate::lie_algebra::StructureConstants;
+
+///
+/// The Killing form of a Lie algebra, computed from its structure constants:
+///
+/// $$g_{ab} = \mathrm{Tr}(\mathrm{ad}_{T_a} \mathrm{ad}_{T_b}) = \sum_{c,d} f_{acd} f_{bdc}$$
+///
+pub fn killing_form(f: &StructureConstants) -> nd::Array2<c64> {
+    // Group the entries f_acd by (c, d), so that every product f_acd f_bdc comes from a pair of
+    // groups (c, d) and (d, c)
+    let mut by_index: HashMap<(usize, usize), Vec<(usize, c64)>> = HashMap::new();
+    for (a, c, d, f_acd) in f.iter() {
+        by_index.entry((c, d)).or_default().push((a, f_acd));
+    }
+
+    let mut g = nd::Array2::zeros((f.dim(), f.dim()));
+    for ((c, d), f_a) in by_index.iter() {
+        if let Some(f_b) = by_index.get(&(*d, *c)) {
+            for (a, f_acd) in f_a {
+                for (b, f_bdc) in f_b {
+                    g[[*a, *b]] += f_acd * f_bdc;
+                }
+            }
+        }
+    }
+    g
+}
+
+///
+/// The trace form of a representation, $B_{ab} = \mathrm{Tr}(T_a T_b)$. For su(n) in the
+/// fundamental representation the Killing form is $2n$ times the trace form.
+///
+pub fn trace_form(basis: &[nd::Array2<c64>]) -> nd::Array2<c64> {
+    let m = basis.len();
+    nd::Array2::from_shape_fn((m, m), |(a, b)| (&basis[a] * &basis[b].t()).sum())
+}
+
+///
+/// A bilinear form is nondegenerate when none of its singular values vanish
+///
+pub fn is_nondegenerate(g: &nd::Array2<c64>) -> bool {
+    let (_, s, _) = g.svd(false, false).unwrap();
+    let s_max = s.iter().cloned().fold(0., f64::max);
+    s_max > 1e-8 && s.iter().all(|x| *x > 1e-8 * s_max)
+}
+
+///
+/// A bilinear form is negative definite when it is real, symmetric and all of its eigenvalues
+/// are negative
+///
+pub fn is_negative_definite(g: &nd::Array2<c64>) -> bool {
+    use approx::AbsDiffEq;
+
+    let is_real = g.iter().all(|x| x.im.abs() < 1e-8);
+    let is_symmetric = g.abs_diff_eq(&g.t(), 1e-8);
+    if !is_real || !is_symmetric {
+        return false;
+    }
+
+    let (e, _) = g.eigh(UPLO::Upper).unwrap();
+    e.iter().all(|x| *x < -1e-8)
+}
+
+///
+/// The signature of a real symmetric bilinear form, as the numbers of positive, negative and
+/// vanishing eigenvalues. The Killing form of a real semisimple algebra is negative on its
+/// compact generators and positive on the noncompact ones, e.g. (3, 3, 0) for the Lorentz
+/// algebra so(3, 1).
+///
+pub fn signature(g: &nd::Array2<c64>) -> (usize, usize, usize) {
+    use approx::AbsDiffEq;
+
+    assert!(
+        g.iter().all(|x| x.im.abs() < 1e-8) && g.abs_diff_eq(&g.t(), 1e-8),
+        "The form is not real and symmetric"
+    );
+
+    let (e, _) = g.mapv(|x| x.re).eigh(UPLO::Upper).unwrap();
+    let e_max = e.iter().map(|x| x.abs()).fold(0., f64::max);
+    let tol = 1e-8 * e_max.max(1.);
+
+    let positive = e.iter().filter(|x| **x > tol).count();
+    let negative = e.iter().filter(|x| **x < -tol).count();
+    (positive, negative, e.len() - positive - negative)
+}
+
+///
+/// Cartan's criterion: a Lie algebra is semisimple if and only if its Killing form is
+/// nondegenerate
+///
+pub fn is_semisimple(f: &StructureConstants) -> bool {
+    is_nondegenerate(&killing_form(f))
+}
+
+///
+/// A real Lie algebra is compact and semisimple when its Killing form is negative definite.
+///
+/// The structure constants have to be those of a real basis, e.g. the anti-Hermitian
+/// `gen_gellmann`. A complex basis such as `gen_sylvester` always returns false.
+///
+pub fn is_compact(f: &StructureConstants) -> bool {
+    is_negative_definite(&killing_form(f))
+}
+
+///
+/// Lowers the last index of the structure constants with a metric:
+///
+/// $$f_{abc} = \sum_d f_{ab}^{\ \ d} g_{dc}$$
+///
+/// With an invariant metric, such as the Killing form or a trace form, $f_{abc}$ is totally
+/// antisymmetric for any basis.
+///
+pub fn lower_index(f: &StructureConstants, g: &nd::Array2<c64>) -> StructureConstants {
+    contract_last(f, g)
+}
+
+///
+/// Raises the last index of $f_{abc}$ with the inverse of the metric:
+///
+/// $$f_{ab}^{\ \ c} = \sum_d f_{abd} g^{dc}$$
+///
+pub fn raise_index(f: &StructureConstants, g: &nd::Array2<c64>) -> StructureConstants {
+    let g_inv = g.inv().expect("The metric is degenerate");
+    contract_last(f, &g_inv)
+}
+
+fn contract_last(f: &StructureConstants, g: &nd::Array2<c64>) -> StructureConstants {
+    let mut res = StructureConstants::new(f.dim());
+    for ((a, b), f_d) in f.pairs() {
+        for c in 0..f.dim() {
+            let x: c64 = f_d.iter().map(|(d, f_abd)| f_abd * g[[*d, c]]).sum();
+            if x.norm() > 1e-8 {
+                res.insert(a, b, c, x);
+            }
+        }
+    }
+    res
+}
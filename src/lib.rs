@@ -1,9 +1,26 @@
+pub mod adjoint;
+pub mod bch;
+pub mod casimir;
+pub mod chevalley;
+pub mod classical;
+pub mod classify;
+pub mod constraints;
+pub mod euler;
+pub mod exponential;
 pub mod gellmann;
+pub mod killing;
 pub mod lie_algebra;
+pub mod random;
+pub mod real_forms;
+pub mod roots;
+pub mod rotation;
 pub mod spherical;
 pub mod su2;
 pub mod sylvester;
 pub mod universal;
+pub mod verify;
+pub mod weingarten;
+pub mod wigner;
 
 #[cfg(test)]
 mod tests {
@@ -64,10 +81,64 @@ mod tests {
     fn test_commutation() {
         let basis = hermitian_basis_from_spin(0.5);
         let x = find_structure_constants(&basis);
-        assert_eq!(x.len(), 3 * 2);
+        assert_eq!(x.pairs().count(), 3 * 2);
 
         let basis = hermitian_basis_from_spin(1.);
         let x = find_structure_constants(&basis);
-        assert_eq!(x.len(), 25 * 2);
+        assert_eq!(x.pairs().count(), 25 * 2);
+    }
+
+    ///
+    /// Every component of a commutator has to be kept, otherwise rebuilding [T_a, T_b] from the
+    /// structure constants fails for any basis that is not orthogonal
+    ///
+    #[test]
+    fn test_commutation_all_components() {
+        use crate::gellmann::gen_gellmann;
+
+        let mut basis = gen_gellmann(3);
+        basis[0] = &basis[0] + &basis[1];
+        basis[4] = &basis[4] - &basis[3];
+        let f = find_structure_constants(&basis);
+
+        assert!(f.pairs().any(|(_, c)| c.len() > 1));
+
+        let n = basis.len();
+        for a in 0..n {
+            for b in 0..n {
+                let mut l_a = ndarray::Array1::zeros(n);
+                let mut l_b = ndarray::Array1::zeros(n);
+                l_a[a] = Complex64::new(1., 0.);
+                l_b[b] = Complex64::new(1., 0.);
+
+                let res = su_commutator(&l_a, &l_b, &f, &basis);
+                let expected = basis[a].dot(&basis[b]) - basis[b].dot(&basis[a]);
+                assert!(res.abs_diff_eq(&expected, 1e-8));
+            }
+        }
+
+        let dense = f.to_dense();
+        assert_eq!(dense.shape(), &[n, n, n]);
+        assert_eq!(StructureConstants::from_dense(&dense, 1e-12), f);
+    }
+
+    #[test]
+    fn test_structure_constants_eq() {
+        let entries = vec![
+            (0, 1, 2, Complex64::new(1., 0.)),
+            (0, 1, 0, Complex64::new(0., 2.)),
+            (1, 0, 2, Complex64::new(-1., 0.)),
+            (0, 1, 1, Complex64::new(3., 0.)),
+        ];
+        let x = StructureConstants::from_entries(3, entries.clone());
+        let y = StructureConstants::from_entries(3, entries.into_iter().rev());
+        assert_eq!(x, y);
+
+        let c: Vec<usize> = x.pair(0, 1).iter().map(|(c, _)| *c).collect();
+        assert_eq!(c, vec![0, 1, 2]);
+        assert_eq!(x.get(0, 1, 1), Complex64::new(3., 0.));
+
+        let z = StructureConstants::from_entries(3, vec![(0, 1, 2, Complex64::new(1., 0.))]);
+        assert_ne!(x, z);
     }
 }
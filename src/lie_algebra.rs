@@ -2,9 +2,122 @@ use ndarray as nd;
 
 use ndarray_linalg::*;
 
+use rayon::prelude::*;
+
 use std::collections::HashMap;
+use std::sync::OnceLock;
+
+///
+/// A sparse rank-3 tensor of coefficients $c_{abc}$, indexed by a pair of basis elements $(a, b)$
+/// and the basis element $c$ of the result.
+///
+/// Only nonzero entries are stored. Every $c$ for a given $(a, b)$ is kept, so products that
+/// expand over several basis elements are represented exactly. The entries of a pair are kept
+/// sorted by $c$, so that two tensors with the same entries compare equal whatever the order in
+/// which they were inserted.
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StructureConstants {
+    dim: usize,
+    entries: HashMap<(usize, usize), Vec<(usize, c64)>>,
+}
 
-use itertools::Itertools;
+impl StructureConstants {
+    /// Create an empty tensor for an algebra of dimension `dim`
+    pub fn new(dim: usize) -> Self {
+        StructureConstants {
+            dim,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Build a tensor from (a, b, c, value) entries
+    pub fn from_entries<I>(dim: usize, entries: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize, usize, c64)>,
+    {
+        let mut x = StructureConstants::new(dim);
+        for (a, b, c, value) in entries {
+            x.insert(a, b, c, value);
+        }
+        x
+    }
+
+    /// Dimension of the algebra, i.e. the range of every index
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// Set the coefficient $c_{abc}$, replacing any previous value
+    pub fn insert(&mut self, a: usize, b: usize, c: usize, value: c64) {
+        assert!(
+            a < self.dim && b < self.dim && c < self.dim,
+            "Index out of range for the structure constants"
+        );
+        let row = self.entries.entry((a, b)).or_default();
+        match row.binary_search_by_key(&c, |(k, _)| *k) {
+            Ok(i) => row[i].1 = value,
+            Err(i) => row.insert(i, (c, value)),
+        }
+    }
+
+    /// Returns $c_{abc}$, which is zero when there is no entry
+    pub fn get(&self, a: usize, b: usize, c: usize) -> c64 {
+        let row = self.pair(a, b);
+        match row.binary_search_by_key(&c, |(k, _)| *k) {
+            Ok(i) => row[i].1,
+            Err(_) => c64::new(0., 0.),
+        }
+    }
+
+    /// Returns every nonzero (c, $c_{abc}$) for the pair (a, b), sorted by c
+    pub fn pair(&self, a: usize, b: usize) -> &[(usize, c64)] {
+        match self.entries.get(&(a, b)) {
+            Some(x) => x.as_slice(),
+            None => &[],
+        }
+    }
+
+    /// Iterate over every pair (a, b) that has at least one nonzero entry
+    pub fn pairs(&self) -> impl Iterator<Item = ((usize, usize), &[(usize, c64)])> {
+        self.entries.iter().map(|(k, v)| (*k, v.as_slice()))
+    }
+
+    /// Iterate over every nonzero entry as (a, b, c, $c_{abc}$)
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, usize, c64)> + '_ {
+        self.entries
+            .iter()
+            .flat_map(|(&(a, b), v)| v.iter().map(move |&(c, x)| (a, b, c, x)))
+    }
+
+    /// Number of nonzero entries
+    pub fn len(&self) -> usize {
+        self.entries.values().map(|x| x.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Convert to a dense array with shape (dim, dim, dim)
+    pub fn to_dense(&self) -> nd::Array3<c64> {
+        let mut x = nd::Array3::zeros((self.dim, self.dim, self.dim));
+        for (a, b, c, value) in self.iter() {
+            x[[a, b, c]] = value;
+        }
+        x
+    }
+
+    /// Build from a dense array, dropping entries with magnitude below `epsilon`
+    pub fn from_dense(x: &nd::Array3<c64>, epsilon: f64) -> Self {
+        let dim = x.shape()[0];
+        let entries = x
+            .indexed_iter()
+            .filter(|(_, v)| v.norm() > epsilon)
+            .map(|((a, b, c), v)| (a, b, c, *v));
+        StructureConstants::from_entries(dim, entries)
+    }
+}
 
 ///
 /// The structure constants of a lie algebra completely specify the product (commutator bracket) of
@@ -13,120 +126,241 @@ use itertools::Itertools;
 /// For example, any two element $T_a$ and $T_b$, the structure constant $f_{abc}$ defines the
 /// relationship:
 ///
-/// $$[T_a, T_b] = i \sum_{c=1}^{n^2 - 1} f_{abc} T_c$$
+/// $$[T_a, T_b] = \sum_{c=1}^{n^2 - 1} f_{abc} T_c$$
 ///
-/// This function solves for $f_{abc}$ and returns a hashmap, with a key (a, b) and value (c,
-/// $f_{abc}$. If $f_{abc} = 0$ then the HashMap will not have an entry.
+/// This function solves for every $f_{abc}$ and returns them as a sparse [`StructureConstants`].
+/// If $f_{abc} = 0$ then there is no entry.
 ///
-pub fn find_structure_constants(
-    basis: &[nd::Array2<c64>],
-) -> HashMap<(usize, usize), (usize, c64)> {
-    use approx::AbsDiffEq;
-    use std::iter::FromIterator;
-
-    let n_dim = basis[0].shape()[0];
-    let n = n_dim * n_dim;
-
+/// Panics if the basis is not closed under the commutator, naming the first pair whose
+/// commutator has a part outside the span of the basis. Use
+/// [`verify_lie_algebra`](crate::verify::verify_lie_algebra) for the residual of every pair.
+///
+pub fn find_structure_constants(basis: &[nd::Array2<c64>]) -> StructureConstants {
     let commutator = |x: &nd::Array2<c64>, y: &nd::Array2<c64>| x.dot(y) - y.dot(x);
 
-    // Rewrite the basis as a matrix with each su(n) element mapped to a column
-    // This then defines the matrix A in Ax=b
-    let matrix: Vec<_> = basis
-        .iter()
-        .map(|x| nd::ArrayView::from_shape(n, x.as_slice().unwrap()).unwrap())
-        .collect();
-    let matrix = nd::stack(nd::Axis(1), matrix.as_slice()).unwrap();
+    let projection = Projection::new(basis.iter());
 
-    // HashMap to store the strucure constants
-    let mut struct_consts = HashMap::new();
-
-    for (i, t_a) in basis.iter().enumerate() {
-        for (j, t_b) in basis.iter().enumerate() {
-            // Ignore when same, since we know they commute
-            if i == j {
-                continue;
+    // The commutator is antisymmetric, so only solve for a < b. Pairs are independent of each
+    // other and are solved in parallel
+    let pairs: Vec<(usize, usize)> = (0..basis.len())
+        .flat_map(|a| (a + 1..basis.len()).map(move |b| (a, b)))
+        .collect();
+    let solutions: Vec<_> = pairs
+        .into_par_iter()
+        .map(|(a, b)| {
+            let c = commutator(&basis[a], &basis[b]);
+            let x = projection.coordinates(&c);
+
+            let mut residual = c.clone();
+            for (x_c, t_c) in x.iter().zip(basis) {
+                residual.scaled_add(-*x_c, t_c);
             }
+            let residual = residual.norm_l2() / c.norm_l2().max(1.);
+            (a, b, x, residual)
+        })
+        .collect();
 
-            let f_t_c = commutator(t_a, t_b);
-
-            let f_t_c = nd::Array::from_iter(f_t_c.iter().cloned());
-
-            // TODO: This is dumb. Perhaps use QR decomposition to solve Ax=b where is A is not
-            // square matrix. Maybe utilize the commutator relationship to simplify work
-            let x = matrix.least_squares(&f_t_c).unwrap();
-            let x = x.solution;
-
-            let idx = x.iter().map(|x| x.abs_diff_ne(&c64::new(0., 0.), 1e-8));
+    if let Some((a, b, _, residual)) = solutions.iter().find(|x| x.3 > 1e-8) {
+        panic!(
+            "The basis is not closed under the commutator: [T_{}, T_{}] is {:e} away from its span",
+            a, b, residual
+        );
+    }
 
-            if idx.clone().filter(|x| *x).count() > 1usize {
-                assert!(true, "This algebra does not satisfiey the lie algebra");
+    let mut struct_consts = StructureConstants::new(basis.len());
+    for (a, b, x, _) in solutions {
+        for (c, x_c) in x.iter().enumerate() {
+            if x_c.norm() > 1e-8 {
+                struct_consts.insert(a, b, c, *x_c);
+                struct_consts.insert(b, a, c, -x_c);
             }
+        }
+    }
 
-            let idx = idx.into_iter().position(|x| x);
+    struct_consts
+}
+
+///
+/// Maps matrices to their coordinates in a basis, by a single matrix $P$ applied to the
+/// flattened matrix.
+///
+/// If the basis is orthogonal under $\mathrm{Tr}(A^\dagger B)$ the coordinates are read off
+/// directly:
+///
+/// $$x_c = \frac{\mathrm{Tr}(T_c^\dagger M)}{\mathrm{Tr}(T_c^\dagger T_c)}$$
+///
+/// Otherwise the basis matrix $A$ is factorized once as $A = QR$ and $P = R^{-1} Q^\dagger$ is
+/// reused for every matrix.
+///
+/// Panics if the basis is not linearly independent, i.e. if a diagonal element of $R$, or the
+/// norm of an element of an orthogonal basis, is negligible next to the largest one.
+///
+pub(crate) struct Projection {
+    p: nd::Array2<c64>,
+}
 
-            if let Some(idx) = idx {
-                struct_consts.insert((i, j), (idx, x[idx]));
+impl Projection {
+    pub(crate) fn new<'a, I>(basis: I) -> Self
+    where
+        I: Iterator<Item = &'a nd::Array2<c64>>,
+    {
+        // Rewrite the basis as a matrix with each element mapped to a column
+        let columns: Vec<nd::Array1<c64>> = basis.map(|x| x.iter().cloned().collect()).collect();
+        let columns: Vec<_> = columns.iter().map(|x| x.view()).collect();
+        let matrix = nd::stack(nd::Axis(1), columns.as_slice()).unwrap();
+
+        let adjoint = matrix.t().mapv(|x| x.conj());
+        let gram = adjoint.dot(&matrix);
+
+        let is_orthogonal = gram
+            .indexed_iter()
+            .all(|((i, j), x)| i == j || x.norm() < 1e-8);
+
+        // Relative to the largest element, below which the basis is rank deficient
+        let is_independent = |norms: &[f64]| {
+            let largest = norms.iter().cloned().fold(0., f64::max);
+            norms.iter().all(|x| *x > 1e-10 * largest)
+        };
+
+        let p = if is_orthogonal {
+            let norms: Vec<f64> = gram.diag().iter().map(|x| x.norm().sqrt()).collect();
+            assert!(
+                is_independent(&norms),
+                "The basis is not linearly independent"
+            );
+            let mut p = adjoint;
+            for (mut row, norm) in p.outer_iter_mut().zip(gram.diag()) {
+                row /= *norm;
             }
-        }
+            p
+        } else {
+            let (q, r) = matrix.qr().unwrap();
+            let norms: Vec<f64> = r.diag().iter().map(|x| x.norm()).collect();
+            assert!(
+                is_independent(&norms),
+                "The basis is not linearly independent"
+            );
+            r.solve_triangular(UPLO::Upper, Diag::NonUnit, &q.t().mapv(|x| x.conj()))
+                .unwrap()
+        };
+
+        Projection { p }
     }
 
-    struct_consts
+    pub(crate) fn coordinates(&self, m: &nd::Array2<c64>) -> nd::Array1<c64> {
+        let m: nd::Array1<c64> = m.iter().cloned().collect();
+        self.p.dot(&m)
+    }
 }
 
-pub fn find_d_coefficients(basis: &[nd::Array2<c64>]) -> HashMap<(usize, usize), (usize, c64)> {
-    use approx::AbsDiffEq;
-    use std::iter::FromIterator;
+///
+/// The symmetric counterpart of the structure constants. The anticommutator of two traceless
+/// basis elements has a component along the identity, so it is kept next to $d_{abc}$:
+///
+/// $$\{T_a, T_b\} = c_{ab} I + \sum_{c} d_{abc} T_c$$
+///
+/// Taking the trace of both sides for a traceless basis of $n \times n$ matrices gives
+/// $c_{ab} = \frac{2}{n} \mathrm{Tr}(T_a T_b)$, e.g. $\frac{4}{3} \delta_{ab}$ for the Gell-Mann
+/// matrices $\lambda_a$.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct DTensor {
+    /// The coefficients $d_{abc}$, symmetric in a and b
+    pub d: StructureConstants,
+    /// The identity coefficients $c_{ab}$, one per pair of basis elements
+    pub identity: nd::Array2<c64>,
+}
 
+///
+/// Solves for the complete anticommutator of every pair of basis elements, including the trace
+/// (identity) part.
+///
+/// When the span of the basis already contains the identity, as for u(n) and gl(n), the whole
+/// anticommutator is expanded in the basis and $c_{ab} = 0$. The result is empty for an empty
+/// basis.
+///
+/// Panics if the basis is not closed under the anticommutator up to the identity, naming the
+/// first pair whose anticommutator has a part outside that span, e.g. for so(n) with $n > 2$.
+///
+pub fn find_d_tensor(basis: &[nd::Array2<c64>]) -> DTensor {
+    let dim = basis.len();
+    if dim == 0 {
+        return DTensor {
+            d: StructureConstants::new(0),
+            identity: nd::Array2::zeros((0, 0)),
+        };
+    }
     let n_dim = basis[0].shape()[0];
-    let n = n_dim * n_dim;
 
     let anti_commutator = |x: &nd::Array2<c64>, y: &nd::Array2<c64>| x.dot(y) + y.dot(x);
+    // The part of a matrix outside the span of some elements, given its coordinates
+    let residual = |m: &nd::Array2<c64>, x: &nd::Array1<c64>, span: &[&nd::Array2<c64>]| {
+        let mut residual = m.clone();
+        for (x_c, t_c) in x.iter().zip(span) {
+            residual.scaled_add(-*x_c, t_c);
+        }
+        residual.norm_l2() / m.norm_l2().max(1.)
+    };
 
+    // The identity is the last element of the extended basis, unless it is already spanned
     let eye: nd::Array2<c64> = nd::Array2::eye(n_dim);
+    let mut span: Vec<&nd::Array2<c64>> = basis.iter().collect();
+    let spans_identity = {
+        let projection = Projection::new(basis.iter());
+        residual(&eye, &projection.coordinates(&eye), &span) < 1e-8
+    };
+    if !spans_identity {
+        span.push(&eye);
+    }
+    let projection = Projection::new(span.iter().cloned());
 
-    // Rewrite the basis as a matrix with each su(n) element mapped to a column
-    // This then defines the matrix A in Ax=b
-    let matrix: Vec<_> = basis
-        .iter()
-        .map(|x| nd::ArrayView::from_shape(n, x.as_slice().unwrap()).unwrap())
+    // The anticommutator is symmetric, so only solve for b >= a
+    let pairs: Vec<(usize, usize)> = (0..dim)
+        .flat_map(|a| (a..dim).map(move |b| (a, b)))
+        .collect();
+    let solutions: Vec<_> = pairs
+        .into_par_iter()
+        .map(|(a, b)| {
+            let m = anti_commutator(&basis[a], &basis[b]);
+            let x = projection.coordinates(&m);
+            let residual = residual(&m, &x, &span);
+            (a, b, x, residual)
+        })
         .collect();
-    let matrix = nd::stack(nd::Axis(1), matrix.as_slice()).unwrap();
-
-    // HashMap to store the strucure constants
-    let mut struct_consts = HashMap::new();
-
-    for (i, t_a) in basis.iter().enumerate() {
-        for (j, t_b) in basis.iter().enumerate() {
-            let mut f_t_c = anti_commutator(t_a, t_b);
-
-            // if i == j {
-            // f_t_c = f_t_c - &eye / c64::new(n_dim as f64, 0.);
-            // }
-
-            let f_t_c = nd::Array::from_iter(f_t_c.iter().cloned());
 
-            // TODO: This is dumb. Perhaps use QR decomposition to solve Ax=b where is A is not
-            // square matrix. Maybe utilize the commutator relationship to simplify work
-            let x = matrix.least_squares(&f_t_c).unwrap();
-            let x = x.solution;
+    if let Some((a, b, _, residual)) = solutions.iter().find(|x| x.3 > 1e-8) {
+        panic!(
+            "The basis is not closed under the anticommutator: {{T_{}, T_{}}} is {:e} away from its span",
+            a, b, residual
+        );
+    }
 
-            let idx = x.iter().map(|x| x.abs_diff_ne(&c64::new(0., 0.), 1e-8));
+    let mut d = StructureConstants::new(dim);
+    let mut identity = nd::Array2::zeros((dim, dim));
 
-            let idx = idx.into_iter().position(|x| x);
+    for (a, b, x, _) in solutions {
+        let (x, c) = x.view().split_at(nd::Axis(0), dim);
+        if let Some(c) = c.first() {
+            identity[[a, b]] = *c;
+            identity[[b, a]] = *c;
+        }
 
-            if let Some(idx) = idx {
-                struct_consts.insert((i, j), (idx, x[idx]));
+        for (k, x_k) in x.iter().enumerate() {
+            if x_k.norm() > 1e-8 {
+                d.insert(a, b, k, *x_k);
+                d.insert(b, a, k, *x_k);
             }
-
-            // for (c, val) in idx.clone().into_iter().enumerate() {
-            //     if val {
-            //         struct_consts.insert((i, j, c), x[c]);
-            //     }
-            // }
         }
     }
 
-    struct_consts
+    DTensor { d, identity }
+}
+
+///
+/// Returns only the $d_{abc}$ part of [`find_d_tensor`]
+///
+pub fn find_d_coefficients(basis: &[nd::Array2<c64>]) -> StructureConstants {
+    find_d_tensor(basis).d
 }
 
 ///
@@ -140,25 +374,18 @@ pub fn find_d_coefficients(basis: &[nd::Array2<c64>]) -> HashMap<(usize, usize),
 /// returns the commutator result [l_a, l_b]
 ///
 pub fn su_commutator(
-    l_a: &ndarray::Array2<c64>,
-    l_b: &ndarray::Array2<c64>,
-    f_ijk: &HashMap<(usize, usize), (usize, c64)>,
+    l_a: &nd::Array1<c64>,
+    l_b: &nd::Array1<c64>,
+    f_ijk: &StructureConstants,
     basis: &[nd::Array2<c64>],
 ) -> nd::Array2<c64> {
     let n_dim = basis[0].shape()[0];
+    let coords = cross(l_a, l_b, f_ijk);
+
     let mut res: nd::Array2<c64> = nd::Array2::zeros((n_dim, n_dim));
-    for (i_i, l_i) in l_a.iter().enumerate() {
-        for (i_j, l_j) in l_b.iter().enumerate() {
-            let (i_k, f) = match f_ijk.get(&(i_i, i_j)) {
-                Some(x) => x,
-                None => continue,
-            };
-            let coeff = l_i * l_j * f;
-
-            if coeff.abs() > 1e-8 {
-                let prod = coeff * basis.get(*i_k).unwrap();
-                res = res + prod;
-            }
+    for (l_c, t_c) in coords.iter().zip(basis) {
+        if l_c.norm() > 1e-8 {
+            res.scaled_add(*l_c, t_c);
         }
     }
     res
@@ -166,85 +393,221 @@ pub fn su_commutator(
 
 ///
 /// Assume a vector space for the su lie algebra, with a vector being defined by the basis. Find
-/// what the commutator is using the coordinates of two matrices defined on the basis.
+/// what the anticommutator is using the coordinates of two matrices defined on the basis.
 /// l_a: coordinates of first matrix
 /// l_b: coordinates of second matrix
-/// f_ijk: structure constants for the lie algebra
+/// d_ijk: d coefficients, with the identity part, for the lie algebra
 /// basis: the vector basis for the lie algebra
 ///
-/// returns the commutator result [l_a, l_b]
+/// returns the anticommutator result {l_a, l_b}
 ///
 pub fn su_anticommutator(
-    l_a: &ndarray::Array2<c64>,
-    l_b: &ndarray::Array2<c64>,
-    d_ijk: &HashMap<(usize, usize), (usize, c64)>,
+    l_a: &nd::Array1<c64>,
+    l_b: &nd::Array1<c64>,
+    d_ijk: &DTensor,
     basis: &[nd::Array2<c64>],
 ) -> nd::Array2<c64> {
     let n_dim = basis[0].shape()[0];
-    let mut res: nd::Array2<c64> = nd::Array2::zeros((n_dim, n_dim));
-    for (i_i, l_i) in l_a.iter().enumerate() {
-        for (i_j, l_j) in l_b.iter().enumerate() {
-            let (i_k, f) = match d_ijk.get(&(i_i, i_j)) {
-                Some(x) => x,
-                None => continue,
-            };
-            let coeff = l_i * l_j * f;
-
-            if coeff.abs() > 1e-8 {
-                let prod = coeff * basis.get(*i_k).unwrap();
-                res = res + prod;
-            }
+    let coords = dot(l_a, l_b, &d_ijk.d);
+
+    // Identity part: sum_ab l_a l_b c_ab
+    let c = l_a.dot(&d_ijk.identity.dot(l_b));
+
+    let mut res: nd::Array2<c64> = c * nd::Array2::eye(n_dim);
+    for (l_c, t_c) in coords.iter().zip(basis) {
+        if l_c.norm() > 1e-8 {
+            res.scaled_add(*l_c, t_c);
         }
     }
     res
 }
 
+///
+/// Compute the coordinates of the commutator [l_a, l_b] given the coordinates of two elements
+/// and the structure constants of the algebra.
+///
 pub fn cross(
-    l_a: &ndarray::Array2<c64>,
-    l_b: &ndarray::Array2<c64>,
-    f_ijk: &HashMap<(usize, usize), (usize, c64)>,
-) -> nd::Array2<c64> {
-    let n_dim = l_a.shape()[0];
-    let mut res: nd::Array2<c64> = nd::Array2::zeros((l_a.shape()[0], l_a.shape()[1]));
-    for (i_i, l_i) in l_a.iter().enumerate() {
-        for (i_j, l_j) in l_b.iter().enumerate() {
-            for k in 0..n_dim {
-                let (i_k, f) = match f_ijk.get(&(i_i, i_j)) {
-                    Some(x) => x,
-                    None => continue,
-                };
-                if k != *i_k {
-                    continue;
-                }
-
-                res[[k, 0]] += f * l_i * l_j;
-            }
+    l_a: &nd::Array1<c64>,
+    l_b: &nd::Array1<c64>,
+    f_ijk: &StructureConstants,
+) -> nd::Array1<c64> {
+    let a: Vec<c64> = l_a.iter().cloned().collect();
+    let b: Vec<c64> = l_b.iter().cloned().collect();
+
+    nd::Array1::from(contract(&a, &b, f_ijk))
+}
+
+/// Computes sum_ab a_a b_b t_abc for every c
+pub(crate) fn contract(a: &[c64], b: &[c64], t_ijk: &StructureConstants) -> Vec<c64> {
+    let mut res = vec![c64::new(0., 0.); t_ijk.dim()];
+    for ((i_i, i_j), t_c) in t_ijk.pairs() {
+        let coeff = a[i_i] * b[i_j];
+        if coeff.norm() == 0. {
+            continue;
+        }
+        for (i_k, t) in t_c {
+            res[*i_k] += t * coeff;
         }
     }
     res
 }
 
+///
+/// Compute the coordinates of the traceless part of the anticommutator {l_a, l_b} given the
+/// coordinates of two elements and the d coefficients of the algebra.
+///
 pub fn dot(
-    l_a: &ndarray::Array2<c64>,
-    l_b: &ndarray::Array2<c64>,
-    d_ijk: &HashMap<(usize, usize), (usize, c64)>,
-) -> nd::Array2<c64> {
-    let n_dim = l_a.shape()[0];
-    let mut res: nd::Array2<c64> = nd::Array2::zeros((l_a.shape()[0], l_a.shape()[1]));
-    for (i_i, l_i) in l_a.iter().enumerate() {
-        for (i_j, l_j) in l_b.iter().enumerate() {
-            for k in 0..n_dim {
-                let (i_k, f) = match d_ijk.get(&(i_i, i_j)) {
-                    Some(x) => x,
-                    None => continue,
-                };
-                if k != *i_k {
-                    continue;
-                }
-
-                res[[k, 0]] += f * l_i * l_j;
-            }
+    l_a: &nd::Array1<c64>,
+    l_b: &nd::Array1<c64>,
+    d_ijk: &StructureConstants,
+) -> nd::Array1<c64> {
+    cross(l_a, l_b, d_ijk)
+}
+
+///
+/// A Lie algebra spanned by a matrix basis.
+///
+/// The structure constants are computed once when the algebra is built. The d coefficients, the
+/// trace metric $g_{ab} = \mathrm{Tr}(T_a T_b)$ and the Killing form are computed the first time
+/// they are needed and cached, and elements of the algebra are handled through their coordinates
+/// in the basis.
+///
+#[derive(Clone, Debug)]
+pub struct LieAlgebra {
+    basis: Vec<nd::Array2<c64>>,
+    f: StructureConstants,
+    d: OnceLock<DTensor>,
+    metric: OnceLock<nd::Array2<c64>>,
+    killing: OnceLock<nd::Array2<c64>>,
+    // Inverse of the Gram matrix Tr(T_a^dagger T_b), used to project onto the basis
+    gram_inv: OnceLock<nd::Array2<c64>>,
+}
+
+impl LieAlgebra {
+    /// Build the algebra from any basis, e.g. `gen_gellmann`, `gen_sylvester` or
+    /// `hermitian_basis_from_spin`
+    pub fn new(basis: Vec<nd::Array2<c64>>) -> Self {
+        assert!(!basis.is_empty(), "The basis is empty");
+
+        let f = find_structure_constants(&basis);
+        Self::from_structure_constants(basis, f)
+    }
+
+    /// Build the algebra from a basis whose structure constants are already known, e.g. exact
+    /// ones, without computing them again
+    pub fn from_structure_constants(basis: Vec<nd::Array2<c64>>, f: StructureConstants) -> Self {
+        assert!(!basis.is_empty(), "The basis is empty");
+        assert_eq!(
+            f.dim(),
+            basis.len(),
+            "The structure constants do not match the basis"
+        );
+
+        LieAlgebra {
+            basis,
+            f,
+            d: OnceLock::new(),
+            metric: OnceLock::new(),
+            killing: OnceLock::new(),
+            gram_inv: OnceLock::new(),
         }
     }
-    res
+
+    /// The simple Lie algebra of a Cartan type such as "A3", "G2" or "E8", in its Chevalley
+    /// basis and with exact integer structure constants. The basis matrices are those of the
+    /// defining representation for the classical algebras, and of the adjoint representation
+    /// otherwise. See [`ChevalleyBasis`](crate::chevalley::ChevalleyBasis).
+    ///
+    /// The low rank coincidences are built as their first type, e.g. "C2" as $B_2$ and "D3" as
+    /// $A_3$. Returns an error for a string that is not the type of a simple Lie algebra.
+    pub fn from_cartan_type(cartan_type: &str) -> Result<Self, String> {
+        let cartan_type = cartan_type.parse()?;
+        let chevalley = crate::chevalley::ChevalleyBasis::new(cartan_type);
+
+        let basis = chevalley
+            .defining_representation()
+            .unwrap_or_else(|| chevalley.adjoint_representation());
+        Ok(Self::from_structure_constants(
+            basis,
+            chevalley.to_structure_constants(),
+        ))
+    }
+
+    /// Number of basis elements
+    pub fn dim(&self) -> usize {
+        self.basis.len()
+    }
+
+    /// Size n of the n x n matrices of the basis
+    pub fn matrix_dim(&self) -> usize {
+        self.basis[0].shape()[0]
+    }
+
+    pub fn basis(&self) -> &[nd::Array2<c64>] {
+        &self.basis
+    }
+
+    pub fn structure_constants(&self) -> &StructureConstants {
+        &self.f
+    }
+
+    pub fn d_tensor(&self) -> &DTensor {
+        self.d.get_or_init(|| find_d_tensor(&self.basis))
+    }
+
+    /// The trace metric $g_{ab} = \mathrm{Tr}(T_a T_b)$
+    pub fn metric(&self) -> &nd::Array2<c64> {
+        self.metric.get_or_init(|| {
+            let m = self.dim();
+            nd::Array2::from_shape_fn((m, m), |(a, b)| (&self.basis[a] * &self.basis[b].t()).sum())
+        })
+    }
+
+    /// The Killing form $\mathrm{Tr}(\mathrm{ad}_{T_a} \mathrm{ad}_{T_b})$
+    pub fn killing_form(&self) -> &nd::Array2<c64> {
+        self.killing
+            .get_or_init(|| crate::killing::killing_form(&self.f))
+    }
+
+    /// Coordinates of the commutator [x, y]
+    pub fn bracket(&self, x: &nd::Array1<c64>, y: &nd::Array1<c64>) -> nd::Array1<c64> {
+        cross(x, y, &self.f)
+    }
+
+    /// The anticommutator {x, y}, returned as the coefficient of the identity and the
+    /// coordinates of the remaining part
+    pub fn anticommutator(
+        &self,
+        x: &nd::Array1<c64>,
+        y: &nd::Array1<c64>,
+    ) -> (c64, nd::Array1<c64>) {
+        let d = self.d_tensor();
+        let c = x.dot(&d.identity.dot(y));
+        (c, dot(x, y, &d.d))
+    }
+
+    /// Coordinates of a matrix in the basis. Any part of the matrix outside of the span of the
+    /// basis is projected out.
+    pub fn to_coordinates(&self, m: &nd::Array2<c64>) -> nd::Array1<c64> {
+        let overlap =
+            nd::Array1::from_shape_fn(self.dim(), |a| (self.basis[a].mapv(|x| x.conj()) * m).sum());
+        let gram_inv = self.gram_inv.get_or_init(|| {
+            let m = self.dim();
+            let gram = nd::Array2::from_shape_fn((m, m), |(a, b)| {
+                (self.basis[a].mapv(|x| x.conj()) * &self.basis[b]).sum()
+            });
+            gram.inv().expect("The basis is not linearly independent")
+        });
+        gram_inv.dot(&overlap)
+    }
+
+    /// The matrix with the given coordinates
+    pub fn from_coordinates(&self, x: &nd::Array1<c64>) -> nd::Array2<c64> {
+        let n = self.matrix_dim();
+        let mut res = nd::Array2::zeros((n, n));
+        for (x_a, t_a) in x.iter().zip(&self.basis) {
+            res.scaled_add(*x_a, t_a);
+        }
+        res
+    }
 }
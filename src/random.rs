@@ -0,0 +1,105 @@
+use ndarray as nd;
+
+use ndarray_linalg::*;
+
+use rand::Rng;
+use rand_distr::StandardNormal;
+
+use crate::lie_algebra::LieAlgebra;
+use crate::real_forms::symplectic_form;
+
+///
+/// Samples a matrix from U(n) with the Haar measure.
+///
+/// A matrix $Z$ of independent complex Gaussians is decomposed as $Z = QR$, and the phases of
+/// the diagonal of $R$ are moved to $Q$, so that the decomposition is unique and $Q$ is Haar
+/// distributed (Mezzadri, "How to generate random matrices from the classical compact groups").
+///
+/// Any [`rand::Rng`] can be used; a seeded one such as `StdRng::seed_from_u64` gives
+/// reproducible samples.
+///
+pub fn haar_unitary<R: Rng + ?Sized>(n: usize, rng: &mut R) -> nd::Array2<c64> {
+    let z = nd::Array2::from_shape_fn((n, n), |_| {
+        let re: f64 = rng.sample(StandardNormal);
+        let im: f64 = rng.sample(StandardNormal);
+        c64::new(re, im) / 2f64.sqrt()
+    });
+    let (q, r) = z.qr().unwrap();
+    let phases = r.diag().mapv(|x| x / x.norm());
+    q * &phases
+}
+
+///
+/// Samples a matrix from SU(n) with the Haar measure, by dividing a Haar unitary by an n-th root
+/// of its determinant
+///
+pub fn haar_special_unitary<R: Rng + ?Sized>(n: usize, rng: &mut R) -> nd::Array2<c64> {
+    let u = haar_unitary(n, rng);
+    let det = u.det().unwrap();
+    let root = c64::from_polar(1., det.arg() / n as f64);
+    u / root
+}
+
+///
+/// Samples a matrix from O(n) with the Haar measure, by the QR decomposition of a real Gaussian
+/// matrix with the signs of the diagonal of $R$ moved to $Q$
+///
+pub fn haar_orthogonal<R: Rng + ?Sized>(n: usize, rng: &mut R) -> nd::Array2<f64> {
+    let z: nd::Array2<f64> = nd::Array2::from_shape_fn((n, n), |_| rng.sample(StandardNormal));
+    let (q, r) = z.qr().unwrap();
+    let signs = r.diag().mapv(f64::signum);
+    q * &signs
+}
+
+///
+/// Samples a matrix from SO(n) with the Haar measure, by flipping the sign of the first row of a
+/// Haar orthogonal matrix of determinant $-1$
+///
+pub fn haar_special_orthogonal<R: Rng + ?Sized>(n: usize, rng: &mut R) -> nd::Array2<f64> {
+    let mut o = haar_orthogonal(n, rng);
+    if o.det().unwrap() < 0. {
+        o.row_mut(0).mapv_inplace(|x| -x);
+    }
+    o
+}
+
+///
+/// Samples a matrix from the compact symplectic group Sp(n) = U(2n) $\cap$ Sp(2n, C) with the
+/// Haar measure, as $2n \times 2n$ unitary matrices with $U^T J U = J$ for `symplectic_form(n)`.
+///
+/// This is the quaternionic version of the QR construction: the columns $u_k$ are obtained by
+/// Gram-Schmidt on complex Gaussian vectors, and each is paired with the column
+/// $u_{n + k} = -J \bar{u}_k$, which spans the quaternionic line of $u_k$. Normalizing every
+/// column to a positive norm plays the role of the phase correction.
+///
+pub fn haar_symplectic<R: Rng + ?Sized>(n: usize, rng: &mut R) -> nd::Array2<c64> {
+    let j = symplectic_form(n);
+    let mut u: nd::Array2<c64> = nd::Array2::zeros((2 * n, 2 * n));
+    for k in 0..n {
+        let mut v = nd::Array1::from_shape_fn(2 * n, |_| {
+            let re: f64 = rng.sample(StandardNormal);
+            let im: f64 = rng.sample(StandardNormal);
+            c64::new(re, im)
+        });
+        for i in (0..k).chain(n..(n + k)) {
+            let column = u.column(i).to_owned();
+            let overlap = column.mapv(|x| x.conj()).dot(&v);
+            v.scaled_add(-overlap, &column);
+        }
+        let norm = v.iter().map(|x| x.norm_sqr()).sum::<f64>().sqrt();
+        let v = v / c64::new(norm, 0.);
+        let partner = -j.dot(&v.mapv(|x| x.conj()));
+        u.column_mut(k).assign(&v);
+        u.column_mut(n + k).assign(&partner);
+    }
+    u
+}
+
+///
+/// A random element of the algebra with independent standard Gaussian coordinates. For a basis
+/// that is orthogonal with equal norms, such as the Gell-Mann matrices of `gen_gellmann`, the
+/// distribution is invariant under the adjoint action.
+///
+pub fn random_element<R: Rng + ?Sized>(algebra: &LieAlgebra, rng: &mut R) -> nd::Array1<c64> {
+    nd::Array1::from_shape_fn(algebra.dim(), |_| c64::new(rng.sample(StandardNormal), 0.))
+}
@@ -0,0 +1,158 @@
+use ndarray as nd;
+use num_complex::Complex64;
+
+use crate::gellmann::gen_gellmann;
+
+///
+/// The metric $\eta = \mathrm{diag}(1, \dots, 1, -1, \dots, -1)$ of signature $(p, q)$
+///
+pub fn signature_metric(p: usize, q: usize) -> nd::Array2<Complex64> {
+    let diag =
+        nd::Array1::from_shape_fn(p + q, |i| Complex64::new(if i < p { 1. } else { -1. }, 0.));
+    nd::Array2::from_diag(&diag)
+}
+
+///
+/// The symplectic form
+///
+/// $$J = \begin{pmatrix} 0 & I \\\\ -I & 0 \end{pmatrix}$$
+///
+/// of size $2n$. The real form sp(2n, R) that preserves it is spanned by
+/// [`gen_sp`](crate::classical::gen_sp), which is already real.
+///
+pub fn symplectic_form(n: usize) -> nd::Array2<Complex64> {
+    let mut j = nd::Array2::zeros((2 * n, 2 * n));
+    for i in 0..n {
+        j[[i, n + i]] = Complex64::new(1., 0.);
+        j[[n + i, i]] = Complex64::new(-1., 0.);
+    }
+    j
+}
+
+///
+/// Returns a basis of so(p, q), the real matrices with $X^T \eta + \eta X = 0$ for the metric
+/// [`signature_metric`]. For every pair $j < k$, in lexicographic order, the basis holds the
+/// rotation $E_{jk} - E_{kj}$ when $\eta_{jj} = \eta_{kk}$, and the boost $E_{jk} + E_{kj}$
+/// otherwise.
+///
+pub fn gen_so_pq(p: usize, q: usize) -> Vec<nd::Array2<Complex64>> {
+    let n = p + q;
+    let mut basis = Vec::with_capacity(n * n.saturating_sub(1) / 2);
+    for j in 0..n {
+        for k in j + 1..n {
+            let sign = if (j < p) == (k < p) { -1. } else { 1. };
+            let mut x = nd::Array2::zeros((n, n));
+            x[[j, k]] = Complex64::new(1., 0.);
+            x[[k, j]] = Complex64::new(sign, 0.);
+            basis.push(x);
+        }
+    }
+    basis
+}
+
+///
+/// Returns a basis of su(p, q), the traceless matrices with $X^\dagger \eta + \eta X = 0$.
+///
+/// It is the `gen_gellmann` basis of su(p + q), where the generators that mix the first $p$ and
+/// the last $q$ coordinates are multiplied by $-i$, which makes them Hermitian (Weyl's unitary
+/// trick). The other generators are anti-Hermitian and span the compact subalgebra $s(u(p)
+/// \oplus u(q))$.
+///
+pub fn gen_su_pq(p: usize, q: usize) -> Vec<nd::Array2<Complex64>> {
+    gen_gellmann(p + q)
+        .into_iter()
+        .map(|x| {
+            let mixes = x
+                .indexed_iter()
+                .any(|((j, k), x)| (j < p) != (k < p) && x.norm() > 0.);
+            if mixes {
+                x * Complex64::new(0., -1.)
+            } else {
+                x
+            }
+        })
+        .collect()
+}
+
+///
+/// Returns the Lorentz algebra so(3, 1) acting on $(x, y, z, t)$ with the metric $\eta =
+/// \mathrm{diag}(1, 1, 1, -1)$, as the rotations $J_x, J_y, J_z$ followed by the boosts $K_x,
+/// K_y, K_z$:
+///
+/// $$[J_i, J_j] = \epsilon_{ijk} J_k, \quad [J_i, K_j] = \epsilon_{ijk} K_k, \quad [K_i, K_j] =
+/// -\epsilon_{ijk} J_k$$
+///
+pub fn gen_lorentz() -> Vec<nd::Array2<Complex64>> {
+    let unit = |entries: &[(usize, usize, f64)]| {
+        let mut x = nd::Array2::zeros((4, 4));
+        for (j, k, v) in entries {
+            x[[*j, *k]] = Complex64::new(*v, 0.);
+        }
+        x
+    };
+
+    vec![
+        unit(&[(2, 1, 1.), (1, 2, -1.)]),
+        unit(&[(0, 2, 1.), (2, 0, -1.)]),
+        unit(&[(1, 0, 1.), (0, 1, -1.)]),
+        unit(&[(0, 3, 1.), (3, 0, 1.)]),
+        unit(&[(1, 3, 1.), (3, 1, 1.)]),
+        unit(&[(2, 3, 1.), (3, 2, 1.)]),
+    ]
+}
+
+///
+/// Returns so(2, 1) acting on $(x, y, t)$ as the rotation $L$ and the boosts $K_x$, $K_y$:
+///
+/// $$[L, K_x] = K_y, \quad [L, K_y] = -K_x, \quad [K_x, K_y] = -L$$
+///
+pub fn gen_so21() -> Vec<nd::Array2<Complex64>> {
+    let lorentz = gen_lorentz();
+    // The restriction of J_z, K_x and K_y to (x, y, t)
+    let restrict = |x: &nd::Array2<Complex64>| {
+        let idx = [0, 1, 3];
+        nd::Array2::from_shape_fn((3, 3), |(j, k)| x[[idx[j], idx[k]]])
+    };
+    vec![
+        restrict(&lorentz[2]),
+        restrict(&lorentz[3]),
+        restrict(&lorentz[4]),
+    ]
+}
+
+///
+/// Returns su(1, 1), the matrices $-i \sigma_z / 2$, $\sigma_x / 2$ and $\sigma_y / 2$ which
+/// preserve $\eta = \sigma_z$. They have the same commutators as [`gen_so21`], so that they
+/// realize the isomorphism su(1, 1) $\cong$ so(2, 1) as the spin 1/2 representation of
+/// so(2, 1).
+///
+pub fn gen_su11() -> Vec<nd::Array2<Complex64>> {
+    let half = |entries: [Complex64; 4]| {
+        nd::Array2::from_shape_vec((2, 2), entries.to_vec()).unwrap() * Complex64::new(0.5, 0.)
+    };
+    let zero = Complex64::new(0., 0.);
+    let one = Complex64::new(1., 0.);
+    let i = Complex64::new(0., 1.);
+
+    vec![
+        half([-i, zero, zero, i]),
+        half([zero, one, one, zero]),
+        half([zero, -i, i, zero]),
+    ]
+}
+
+///
+/// Whether a group element preserves a bilinear or sesquilinear form, $g^\dagger \eta g = \eta$.
+/// For the real groups SO(p, q) and Sp(2n, R) this is the same as $g^T \eta g = \eta$.
+///
+pub fn preserves_form(g: &nd::Array2<Complex64>, form: &nd::Array2<Complex64>) -> bool {
+    use approx::AbsDiffEq;
+
+    let g_dagger = g.t().mapv(|x| x.conj());
+    let scale = form.iter().map(|x| x.norm()).fold(1., f64::max)
+        * g.iter().map(|x| x.norm_sqr()).sum::<f64>();
+    g_dagger
+        .dot(form)
+        .dot(g)
+        .abs_diff_eq(form, 1e-8 * scale.max(1.))
+}
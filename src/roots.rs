@@ -0,0 +1,282 @@
+use ndarray as nd;
+
+use ndarray_linalg::*;
+
+use crate::adjoint::adjoint_matrix;
+use crate::lie_algebra::LieAlgebra;
+
+///
+/// The roots of a semisimple Lie algebra with respect to a Cartan subalgebra $\mathfrak{h} =
+/// \mathrm{span}(H_1, \dots, H_r)$.
+///
+/// A root $\alpha$ is stored as the vector $(\alpha(H_1), \dots, \alpha(H_r))$, multiplied by a
+/// common phase so that it is real. For a compact basis such as `gen_gellmann` the eigenvalues
+/// of $\mathrm{ad}_{H_i}$ are imaginary, and that phase is $-i$. When no common phase makes the
+/// roots real, as for `gen_sylvester`, the Cartan basis is replaced by the elements $H_k$ with
+/// $K(H_k, H) = \beta_k(H)$ for $r$ independent roots $\beta_k$.
+///
+/// Inner products of roots are those induced by the Killing form, see
+/// [`RootSystem::inner_product`].
+///
+#[derive(Clone, Debug)]
+pub struct RootSystem {
+    /// Coordinates of $H_1, \dots, H_r$ in the basis of the algebra
+    pub cartan: Vec<nd::Array1<c64>>,
+    /// Every root
+    pub roots: Vec<nd::Array1<f64>>,
+    /// Coordinates of the root vector $E_\alpha$ of every root, with unit norm
+    pub root_vectors: Vec<nd::Array1<c64>>,
+    pub positive_roots: Vec<nd::Array1<f64>>,
+    pub simple_roots: Vec<nd::Array1<f64>>,
+    /// The coroots $\alpha^\vee = 2 \alpha / (\alpha, \alpha)$ of the simple roots
+    pub coroots: Vec<nd::Array1<f64>>,
+    /// The positive root of largest height. For an algebra that is not simple, this is the
+    /// highest root of one of its simple components
+    pub highest_root: nd::Array1<f64>,
+    /// The inner product on roots, the inverse of the Killing form restricted to the Cartan
+    /// subalgebra
+    pub metric: nd::Array2<f64>,
+}
+
+impl RootSystem {
+    /// Rank of the algebra, the dimension of the Cartan subalgebra
+    pub fn rank(&self) -> usize {
+        self.cartan.len()
+    }
+
+    /// $(\alpha, \beta)$
+    pub fn inner_product(&self, a: &nd::Array1<f64>, b: &nd::Array1<f64>) -> f64 {
+        a.dot(&self.metric.dot(b))
+    }
+
+    /// Coefficients of a root in the basis of simple roots, which are integers
+    pub fn simple_root_coefficients(&self, root: &nd::Array1<f64>) -> nd::Array1<f64> {
+        let columns: Vec<_> = self.simple_roots.iter().map(|x| x.view()).collect();
+        let simple = nd::stack(nd::Axis(1), columns.as_slice()).unwrap();
+        simple.solve(root).unwrap()
+    }
+
+    /// Position of a root in `roots`
+    pub fn find_root(&self, root: &nd::Array1<f64>) -> Option<usize> {
+        find(&self.roots, root)
+    }
+}
+
+fn find(roots: &[nd::Array1<f64>], root: &nd::Array1<f64>) -> Option<usize> {
+    use approx::AbsDiffEq;
+    roots.iter().position(|x| x.abs_diff_eq(root, 1e-6))
+}
+
+// Deterministic coefficients with no simple relation between them, which make a combination of
+// elements generic. Square roots of distinct squarefree integers are linearly independent over
+// the rationals, unlike the terms of a Weyl sequence k x mod 1
+fn generic(i: usize) -> f64 {
+    let is_squarefree = |n: usize| (2..).take_while(|k| k * k <= n).all(|k| n % (k * k) != 0);
+    let n = (2..).filter(|n| is_squarefree(*n)).nth(i).unwrap();
+    0.5 + (n as f64).sqrt().fract()
+}
+
+// The numerical rank of a matrix
+fn rank(m: &nd::Array2<c64>) -> usize {
+    let (_, s, _) = m.svd(false, false).unwrap();
+    let s_max = s.iter().cloned().fold(0., f64::max);
+    s.iter().filter(|x| **x > 1e-8 * s_max.max(1.)).count()
+}
+
+// A matrix is diagonalizable when its eigenvectors span the whole space
+fn is_diagonalizable(m: &nd::Array2<c64>) -> bool {
+    let (_, v) = m.eig().unwrap();
+    let (_, s, _) = v.svd(false, false).unwrap();
+    let s_max = s.iter().cloned().fold(0., f64::max);
+    s.iter().all(|x| *x > 1e-6 * s_max)
+}
+
+///
+/// Finds a Cartan subalgebra, a maximal set of commuting and diagonalizable elements, and
+/// returns the coordinates of its basis.
+///
+/// The rank is the dimension of the centralizer of a generic element. Basis elements are taken
+/// first whenever they already form such a set, so that e.g. the diagonal generators of
+/// `gen_gellmann` are found. Otherwise the centralizer of a generic element is used.
+///
+pub fn cartan_subalgebra(algebra: &LieAlgebra) -> Vec<nd::Array1<c64>> {
+    let m = algebra.dim();
+    let f = algebra.structure_constants();
+
+    let x = nd::Array1::from_shape_fn(m, |a| c64::new(generic(a), 0.));
+    let ad_x = adjoint_matrix(algebra, &x);
+    let r = m - rank(&ad_x);
+
+    let mut chosen: Vec<usize> = Vec::with_capacity(r);
+    for (a, t_a) in algebra.basis().iter().enumerate() {
+        if chosen.len() == r {
+            break;
+        }
+        let commutes = chosen.iter().all(|b| f.pair(a, *b).is_empty());
+        if commutes && is_diagonalizable(t_a) {
+            chosen.push(a);
+        }
+    }
+
+    if chosen.len() == r {
+        chosen
+            .iter()
+            .map(|a| {
+                let mut h = nd::Array1::zeros(m);
+                h[*a] = c64::new(1., 0.);
+                h
+            })
+            .collect()
+    } else {
+        // The kernel of ad_x, from the right singular vectors with vanishing singular values
+        let (_, s, vt) = ad_x.svd(false, true).unwrap();
+        let vt = vt.unwrap();
+        let s_max = s.iter().cloned().fold(0., f64::max);
+        (0..m)
+            .filter(|i| *i >= s.len() || s[*i] <= 1e-8 * s_max.max(1.))
+            .map(|i| vt.row(i).mapv(|x| x.conj()))
+            .collect()
+    }
+}
+
+///
+/// Computes the root system of a semisimple Lie algebra by simultaneously diagonalizing the
+/// adjoint action of a Cartan subalgebra.
+///
+/// Positive roots are those with a positive product with a fixed generic vector, and simple
+/// roots are the positive roots that are not the sum of two positive roots.
+///
+pub fn root_system(algebra: &LieAlgebra) -> RootSystem {
+    let cartan = cartan_subalgebra(algebra);
+    let r = cartan.len();
+
+    let ad_h: Vec<nd::Array2<c64>> = cartan.iter().map(|h| adjoint_matrix(algebra, h)).collect();
+
+    // A generic element of the Cartan subalgebra has distinct eigenvalues on every root space
+    let mut ad_generic = nd::Array2::zeros(ad_h[0].raw_dim());
+    for (i, ad) in ad_h.iter().enumerate() {
+        ad_generic.scaled_add(c64::new(generic(i), 0.), ad);
+    }
+    let (e, v) = ad_generic.eig().unwrap();
+    let e_max = e.iter().map(|x| x.norm()).fold(0., f64::max);
+
+    let mut complex_roots = Vec::new();
+    let mut root_vectors = Vec::new();
+    for (e_k, v_k) in e.iter().zip(v.gencolumns()) {
+        if e_k.norm() <= 1e-8 * e_max.max(1.) {
+            continue;
+        }
+        let norm = v_k.iter().map(|x| x.norm_sqr()).sum::<f64>().sqrt();
+        let v_k = v_k.mapv(|x| x / norm);
+        let v_conj = v_k.mapv(|x| x.conj());
+
+        let alpha = nd::Array1::from_shape_fn(r, |i| v_conj.dot(&ad_h[i].dot(&v_k)));
+        complex_roots.push(alpha);
+        root_vectors.push(v_k);
+    }
+
+    let killing = algebra.killing_form();
+    let g = nd::Array2::from_shape_fn((r, r), |(i, j)| cartan[i].dot(&killing.dot(&cartan[j])));
+    let g_inv = g.inv().expect("The algebra is not semisimple");
+
+    // Rotate the roots by a common phase so that they are real
+    let re: f64 = complex_roots.iter().flatten().map(|x| x.re * x.re).sum();
+    let im: f64 = complex_roots.iter().flatten().map(|x| x.im * x.im).sum();
+    let phase = if im > re {
+        c64::new(0., -1.)
+    } else {
+        c64::new(1., 0.)
+    };
+    let is_real = complex_roots
+        .iter()
+        .flatten()
+        .all(|x| (x * phase).im.abs() < 1e-8);
+
+    let (cartan, roots, metric) = if is_real {
+        let roots: Vec<nd::Array1<f64>> = complex_roots
+            .iter()
+            .map(|alpha| alpha.mapv(|x| (x * phase).re))
+            .collect();
+        // The inverse of the Killing form restricted to the Cartan subalgebra
+        let metric = g_inv.mapv(|x| (x / (phase * phase)).re);
+        (cartan, roots, metric)
+    } else {
+        // Change the Cartan basis to H_k with K(H_k, H) = beta_k(H) for r independent roots
+        // beta_k, on which every root is real
+        let mut betas: Vec<nd::Array1<c64>> = Vec::with_capacity(r);
+        for alpha in complex_roots.iter() {
+            let mut candidate = betas.clone();
+            candidate.push(alpha.clone());
+            let columns: Vec<_> = candidate.iter().map(|x| x.view()).collect();
+            if rank(&nd::stack(nd::Axis(1), columns.as_slice()).unwrap()) == candidate.len() {
+                betas = candidate;
+            }
+            if betas.len() == r {
+                break;
+            }
+        }
+        let duals: Vec<nd::Array1<c64>> = betas.iter().map(|beta| g_inv.dot(beta)).collect();
+
+        let new_cartan = duals
+            .iter()
+            .map(|dual| {
+                let mut h = nd::Array1::zeros(algebra.dim());
+                for (h_i, dual_i) in cartan.iter().zip(dual) {
+                    h.scaled_add(*dual_i, h_i);
+                }
+                h
+            })
+            .collect();
+        let roots = complex_roots
+            .iter()
+            .map(|alpha| nd::Array1::from_shape_fn(r, |k| alpha.dot(&duals[k]).re))
+            .collect();
+        let gram = nd::Array2::from_shape_fn((r, r), |(k, l)| betas[k].dot(&duals[l]));
+        let metric = gram.inv().unwrap().mapv(|x| x.re);
+        (new_cartan, roots, metric)
+    };
+
+    let w = nd::Array1::from_shape_fn(r, |i| generic(i + r));
+    let positive_roots: Vec<nd::Array1<f64>> = roots
+        .iter()
+        .filter(|alpha| alpha.dot(&w) > 0.)
+        .cloned()
+        .collect();
+
+    let simple_roots: Vec<nd::Array1<f64>> = positive_roots
+        .iter()
+        .filter(|alpha| {
+            positive_roots
+                .iter()
+                .all(|beta| find(&positive_roots, &(*alpha - beta)).is_none())
+        })
+        .cloned()
+        .collect();
+
+    let mut system = RootSystem {
+        cartan,
+        roots,
+        root_vectors,
+        positive_roots,
+        simple_roots,
+        coroots: Vec::new(),
+        highest_root: nd::Array1::zeros(r),
+        metric,
+    };
+
+    system.coroots = system
+        .simple_roots
+        .iter()
+        .map(|alpha| alpha * (2. / system.inner_product(alpha, alpha)))
+        .collect();
+
+    let height = |alpha: &nd::Array1<f64>| system.simple_root_coefficients(alpha).sum();
+    system.highest_root = system
+        .positive_roots
+        .iter()
+        .max_by(|x, y| height(x).partial_cmp(&height(y)).unwrap())
+        .unwrap()
+        .clone();
+
+    system
+}
@@ -0,0 +1,288 @@
+use ndarray as nd;
+
+use ndarray_linalg::*;
+
+use std::ops::Mul;
+
+///
+/// A unit quaternion $q = w + x i + y j + z k$, for the rotation by $\theta$ around the unit
+/// axis $n$ with $w = \cos \frac{\theta}{2}$ and $(x, y, z) = \sin \frac{\theta}{2} n$.
+///
+/// The quaternions $q$ and $-q$ are the same rotation of SO(3), but different elements of
+/// SU(2): a quaternion is the spin 1/2 rotation
+/// $$U = e^{-i \theta n \cdot \vec{S}} = w I - i (x \sigma_x + y \sigma_y + z \sigma_z)$$
+/// with $\vec{S} = \vec{\sigma} / 2$ in the ordering of `su2::gen_su2(0.5)`, $m = 1/2$ first.
+/// The conversions to and from SU(2) are therefore lossless, and the product of quaternions is
+/// the product of the matrices.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quaternion {
+    pub w: f64,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Quaternion {
+    pub fn new(w: f64, x: f64, y: f64, z: f64) -> Self {
+        Quaternion { w, x, y, z }
+    }
+
+    pub fn identity() -> Self {
+        Quaternion::new(1., 0., 0., 0.)
+    }
+
+    /// The rotation by `angle` around `axis`, which does not need to be normalized
+    pub fn from_axis_angle(axis: [f64; 3], angle: f64) -> Self {
+        let norm = axis.iter().map(|x| x * x).sum::<f64>().sqrt();
+        assert!(norm > 0., "The axis of a rotation cannot be zero");
+        let s = (angle / 2.).sin() / norm;
+        Quaternion::new((angle / 2.).cos(), s * axis[0], s * axis[1], s * axis[2])
+    }
+
+    /// The unit axis and the angle in $[0, 2\pi]$ of the rotation. The axis of the identity, or
+    /// of $-1$ with angle $2\pi$, is the z axis.
+    pub fn to_axis_angle(&self) -> ([f64; 3], f64) {
+        let norm = (self.x * self.x + self.y * self.y + self.z * self.z).sqrt();
+        let angle = 2. * norm.atan2(self.w);
+        if norm == 0. {
+            return ([0., 0., 1.], angle);
+        }
+        ([self.x / norm, self.y / norm, self.z / norm], angle)
+    }
+
+    /// The quaternion of a matrix of SU(2). Panics if the matrix is not in SU(2).
+    pub fn from_su2(u: &nd::Array2<c64>) -> Self {
+        use approx::AbsDiffEq;
+
+        assert_eq!(u.shape(), &[2, 2], "The matrix is not 2 x 2");
+        let u_dagger = u.t().mapv(|x| x.conj());
+        assert!(
+            u_dagger.dot(u).abs_diff_eq(&nd::Array2::eye(2), 1e-8)
+                && u.det().unwrap().abs_diff_eq(&c64::new(1., 0.), 1e-8),
+            "The matrix is not in SU(2)"
+        );
+        // U_00 = w - iz and U_10 = y - ix
+        Quaternion::new(u[[0, 0]].re, -u[[1, 0]].im, u[[1, 0]].re, -u[[0, 0]].im)
+    }
+
+    /// The spin 1/2 rotation $w I - i (x \sigma_x + y \sigma_y + z \sigma_z)$
+    pub fn to_su2(&self) -> nd::Array2<c64> {
+        nd::arr2(&[
+            [c64::new(self.w, -self.z), c64::new(-self.y, -self.x)],
+            [c64::new(self.y, -self.x), c64::new(self.w, self.z)],
+        ])
+    }
+
+    /// The rotation matrix $R$ of SO(3), with $U (v \cdot \vec{\sigma}) U^\dagger = (R v) \cdot
+    /// \vec{\sigma}$ for the SU(2) matrix $U$ of the quaternion
+    pub fn to_rotation_matrix(&self) -> nd::Array2<f64> {
+        let Quaternion { w, x, y, z } = *self;
+        nd::arr2(&[
+            [
+                1. - 2. * (y * y + z * z),
+                2. * (x * y - w * z),
+                2. * (x * z + w * y),
+            ],
+            [
+                2. * (x * y + w * z),
+                1. - 2. * (x * x + z * z),
+                2. * (y * z - w * x),
+            ],
+            [
+                2. * (x * z - w * y),
+                2. * (y * z + w * x),
+                1. - 2. * (x * x + y * y),
+            ],
+        ])
+    }
+
+    ///
+    /// Lifts a rotation matrix of SO(3) to one of its two quaternions.
+    ///
+    /// Without a reference, the quaternion with $w \geq 0$ is returned, which jumps to the other
+    /// sign when the angle of the rotation crosses $\pi$. With a reference, such as the lift of
+    /// the previous rotation along a path, the quaternion closest to the reference is returned,
+    /// so that the lift of a continuous path is continuous. See [`lift_path`].
+    ///
+    /// Panics if the matrix is not in SO(3).
+    ///
+    pub fn from_rotation_matrix(r: &nd::Array2<f64>, reference: Option<&Quaternion>) -> Self {
+        use approx::AbsDiffEq;
+
+        assert_eq!(r.shape(), &[3, 3], "The matrix is not 3 x 3");
+        assert!(
+            r.t().dot(r).abs_diff_eq(&nd::Array2::eye(3), 1e-8)
+                && (r.det().unwrap() - 1.).abs() < 1e-8,
+            "The matrix is not in SO(3)"
+        );
+
+        // Shepperd's method: divide by the largest of 4w^2, 4x^2, 4y^2 and 4z^2
+        let trace = r[[0, 0]] + r[[1, 1]] + r[[2, 2]];
+        let candidates = [trace, r[[0, 0]], r[[1, 1]], r[[2, 2]]];
+        let largest = (0..4)
+            .max_by(|a, b| candidates[*a].partial_cmp(&candidates[*b]).unwrap())
+            .unwrap();
+        let q = match largest {
+            0 => {
+                let s = 2. * (1. + trace).sqrt();
+                Quaternion::new(
+                    s / 4.,
+                    (r[[2, 1]] - r[[1, 2]]) / s,
+                    (r[[0, 2]] - r[[2, 0]]) / s,
+                    (r[[1, 0]] - r[[0, 1]]) / s,
+                )
+            }
+            1 => {
+                let s = 2. * (1. + r[[0, 0]] - r[[1, 1]] - r[[2, 2]]).sqrt();
+                Quaternion::new(
+                    (r[[2, 1]] - r[[1, 2]]) / s,
+                    s / 4.,
+                    (r[[0, 1]] + r[[1, 0]]) / s,
+                    (r[[0, 2]] + r[[2, 0]]) / s,
+                )
+            }
+            2 => {
+                let s = 2. * (1. - r[[0, 0]] + r[[1, 1]] - r[[2, 2]]).sqrt();
+                Quaternion::new(
+                    (r[[0, 2]] - r[[2, 0]]) / s,
+                    (r[[0, 1]] + r[[1, 0]]) / s,
+                    s / 4.,
+                    (r[[1, 2]] + r[[2, 1]]) / s,
+                )
+            }
+            _ => {
+                let s = 2. * (1. - r[[0, 0]] - r[[1, 1]] + r[[2, 2]]).sqrt();
+                Quaternion::new(
+                    (r[[1, 0]] - r[[0, 1]]) / s,
+                    (r[[0, 2]] + r[[2, 0]]) / s,
+                    (r[[1, 2]] + r[[2, 1]]) / s,
+                    s / 4.,
+                )
+            }
+        }
+        .normalize();
+
+        let flip = match reference {
+            Some(reference) => q.dot(reference) < 0.,
+            None => q.w < 0.,
+        };
+        if flip {
+            -q
+        } else {
+            q
+        }
+    }
+
+    pub fn dot(&self, other: &Quaternion) -> f64 {
+        self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn norm(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    pub fn normalize(&self) -> Self {
+        let norm = self.norm();
+        Quaternion::new(self.w / norm, self.x / norm, self.y / norm, self.z / norm)
+    }
+
+    /// The inverse rotation, $\bar{q} = w - x i - y j - z k$ for a unit quaternion
+    pub fn conjugate(&self) -> Self {
+        Quaternion::new(self.w, -self.x, -self.y, -self.z)
+    }
+
+    ///
+    /// The spherical linear interpolation from `self` at `t = 0` to `other` at `t = 1`, along
+    /// the geodesic of SU(2) at constant angular velocity.
+    ///
+    /// The geodesic goes through the quaternions themselves, so it is the shortest path between
+    /// the rotations only when `self.dot(other) >= 0`; negate `other` otherwise, for example
+    /// with the lift of [`Quaternion::from_rotation_matrix`] with `self` as reference.
+    ///
+    /// For `other` $= -$`self` every great circle through both is a geodesic, and the one through
+    /// $i q$ is taken, i.e. the rotation by $2 \pi t$ around the x axis composed with `self`.
+    ///
+    pub fn slerp(&self, other: &Quaternion, t: f64) -> Self {
+        let combine = |a: f64, p: &Quaternion, b: f64, q: &Quaternion| {
+            Quaternion::new(
+                a * p.w + b * q.w,
+                a * p.x + b * q.x,
+                a * p.y + b * q.y,
+                a * p.z + b * q.z,
+            )
+            .normalize()
+        };
+
+        let cos = self.dot(other).clamp(-1., 1.);
+        let omega = cos.acos();
+        if omega.sin().abs() >= 1e-9 {
+            let (a, b) = (
+                ((1. - t) * omega).sin() / omega.sin(),
+                (t * omega).sin() / omega.sin(),
+            );
+            combine(a, self, b, other)
+        } else if cos > 0. {
+            // Nearly parallel, where the interpolation is linear
+            combine(1. - t, self, t, other)
+        } else {
+            // Nearly opposite, where the linear interpolation goes through 0: follow the great
+            // circle through the orthogonal quaternion i q instead
+            let orthogonal = Quaternion::new(-self.x, self.w, -self.z, self.y);
+            let angle = std::f64::consts::PI * t;
+            combine(angle.cos(), self, angle.sin(), &orthogonal)
+        }
+    }
+}
+
+impl std::ops::Neg for Quaternion {
+    type Output = Quaternion;
+
+    fn neg(self) -> Quaternion {
+        Quaternion::new(-self.w, -self.x, -self.y, -self.z)
+    }
+}
+
+/// The composition of rotations, applying `other` first: the Hamilton product, which is the
+/// product of the SU(2) matrices and of the rotation matrices
+impl Mul for Quaternion {
+    type Output = Quaternion;
+
+    fn mul(self, other: Quaternion) -> Quaternion {
+        let (a, b) = (self, other);
+        Quaternion::new(
+            a.w * b.w - a.x * b.x - a.y * b.y - a.z * b.z,
+            a.w * b.x + a.x * b.w + a.y * b.z - a.z * b.y,
+            a.w * b.y - a.x * b.z + a.y * b.w + a.z * b.x,
+            a.w * b.z + a.x * b.y - a.y * b.x + a.z * b.w,
+        )
+    }
+}
+
+///
+/// Lifts a path of rotation matrices to SU(2), choosing the sign of every quaternion to be the
+/// closest to the previous one. The first rotation is lifted with $w \geq 0$.
+///
+pub fn lift_path(rotations: &[nd::Array2<f64>]) -> Vec<Quaternion> {
+    let mut res: Vec<Quaternion> = Vec::with_capacity(rotations.len());
+    for r in rotations.iter() {
+        let q = Quaternion::from_rotation_matrix(r, res.last());
+        res.push(q);
+    }
+    res
+}
+
+///
+/// The rotation matrix of SO(3) of a matrix of SU(2)
+///
+pub fn su2_to_so3(u: &nd::Array2<c64>) -> nd::Array2<f64> {
+    Quaternion::from_su2(u).to_rotation_matrix()
+}
+
+///
+/// Lifts a rotation matrix of SO(3) to SU(2), as in [`Quaternion::from_rotation_matrix`]
+///
+pub fn so3_to_su2(r: &nd::Array2<f64>, reference: Option<&nd::Array2<c64>>) -> nd::Array2<c64> {
+    let reference = reference.map(Quaternion::from_su2);
+    Quaternion::from_rotation_matrix(r, reference.as_ref()).to_su2()
+}
@@ -35,12 +35,15 @@ pub fn c_g(r: i32, u: i32, la: i32) -> f64 {
     }
 }
 
+///
+/// The spherical components of the spin, $Q_{1,0} = J_z$ and $Q_{1,\pm 1} = \mp J_\pm / \sqrt{2}$
+///
 pub fn q_1_u(j: f64, u: i32) -> nd::Array2<f64> {
     use su2::{s_x, s_y, s_z};
     match u {
         0 => s_z(j),
-        1 => -(s_x(j) + s_y(j)),
-        -1 => (s_x(j) - s_y(j)),
+        1 => -(s_x(j) + s_y(j)) / 2f64.sqrt(),
+        -1 => (s_x(j) - s_y(j)) / 2f64.sqrt(),
         _ => panic!("Bad input for u"),
     }
 }
@@ -48,40 +51,64 @@ pub fn q_1_u(j: f64, u: i32) -> nd::Array2<f64> {
 pub fn q_r_u(j: f64, r: i32, u: i32) -> nd::Array2<f64> {
     if r == 1 {
         q_1_u(j, u)
-    } else {
+    } else if u.abs() > r {
         let n = (j * 2. + 1.) as usize;
+        nd::Array2::zeros((n, n))
+    } else {
+        let mut tensors = spherical_tensors(j, r);
+        tensors.pop().unwrap().swap_remove((u + r) as usize)
+    }
+}
 
-        let mut mat: nd::Array2<f64> = nd::Array2::zeros((n, n));
-
-        for i in (-1 + u)..=(u + 1) {
-            if i.abs() < r {
-                let q1u = q_1_u(j, -i + u);
-                mat = mat + c_g(r - 1, i, -i + u) * q_r_u(j, r - 1, i).dot(&q1u);
-            }
-        }
-        mat
+///
+/// The spherical tensor operators $Q_{r,u}$ of every rank up to `max_rank`, where
+/// `tensors[r - 1][u + r]` is $Q_{r,u}$.
+///
+/// Each rank is built once from the previous one:
+///
+/// $$Q_{r,u} = \sum_{i} \langle r - 1, i; 1, u - i | r, u \rangle Q_{r-1,i} Q_{1,u-i}$$
+///
+pub fn spherical_tensors(j: f64, max_rank: i32) -> Vec<Vec<nd::Array2<f64>>> {
+    if max_rank < 1 {
+        return Vec::new();
+    }
+    let n = (j * 2. + 1.) as usize;
+
+    let q1: Vec<nd::Array2<f64>> = (-1..=1).map(|u| q_1_u(j, u)).collect();
+
+    let mut tensors = vec![q1.clone()];
+
+    for r in 2..=max_rank {
+        let prev = &tensors[(r - 2) as usize];
+        let rank = (-r..=r)
+            .map(|u| {
+                let mut mat: nd::Array2<f64> = nd::Array2::zeros((n, n));
+                for i in (-1 + u)..=(u + 1) {
+                    if i.abs() < r {
+                        let q1u = &q1[(-i + u + 1) as usize];
+                        let q = &prev[(i + r - 1) as usize];
+                        mat = mat + c_g(r - 1, i, -i + u) * q.dot(q1u);
+                    }
+                }
+                mat
+            })
+            .collect();
+        tensors.push(rank);
     }
+    tensors
 }
 
 pub fn basis_from_spin(j: f64) -> Vec<nd::Array2<f64>> {
-    let n = (j * 2. + 1.) as u32;
+    let n = (j * 2. + 1.) as i32;
 
-    let mut basis = Vec::with_capacity(2usize.pow(n) - 1);
-
-    for r in 1..(n as i32) {
-        for u in -r..=r {
-            let x = q_r_u(j, r, u);
-            basis.push(x);
-        }
-    }
-    basis
+    spherical_tensors(j, n - 1).into_iter().flatten().collect()
 }
 
 pub fn hermitian_basis_from_spin(j: f64) -> Vec<nd::Array2<Complex64>> {
     let n = (j * 2. + 1.) as u32;
     let basis = basis_from_spin(j);
 
-    let mut herm_basis: Vec<nd::Array2<Complex64>> = Vec::with_capacity(2usize.pow(n) - 1);
+    let mut herm_basis: Vec<nd::Array2<Complex64>> = Vec::with_capacity((n * n - 1) as usize);
 
     let mut i = 0;
 
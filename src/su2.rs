@@ -64,9 +64,6 @@ use num_complex::Complex64;
 /// Returns matrix representations of s_z, s_x, s_y for spin j
 /// For convenience, these matrices are returned as Complex
 pub fn gen_su2(j: f64) -> [nd::Array2<Complex64>; 3] {
-    let _i = Complex64::new(0., 1.);
-    let _1 = Complex64::new(1., 0.);
-
     let s_z = s_z(j).map(|&x| Complex64::new(x, 0.));
     let s_x = s_x(j).map(|&x| Complex64::new(x, 0.));
     let s_y = s_y(j).map(|&x| Complex64::new(x, 0.));
@@ -3,6 +3,8 @@ use ndarray as nd;
 use num_complex::Complex64;
 use std::f64::consts::PI;
 
+use crate::lie_algebra::StructureConstants;
+
 ///
 /// Returns a su(d) basis which is generated by Sylvester's CLOCK and SHIFT matrices. Clock and
 /// Shift are generalization of pauli_x and pauli_z in the su(2) case.
@@ -47,3 +49,123 @@ pub fn gen_sylvester(d: usize) -> Vec<nd::Array2<Complex64>> {
 
     basis
 }
+
+///
+/// The basis of `gen_sylvester(d)` labelled by the powers $(r, s)$ of each Weyl operator $X^r
+/// Z^s$, with $r$ and $s$ taken mod d.
+///
+/// Since $Z X = \omega X Z$ with $\omega = e^{2 \pi i / d}$, the product of two basis elements is
+/// a phase times another one:
+///
+/// $$X^r Z^s X^{r'} Z^{s'} = \omega^{s r'} X^{r + r'} Z^{s + s'}$$
+///
+/// so the products and commutators are known without any matrix multiplication.
+///
+#[derive(Clone, Debug)]
+pub struct WeylBasis {
+    d: usize,
+    labels: Vec<(usize, usize)>,
+    // Index of the label (r, s) at position r * d + s, None for the identity
+    indices: Vec<Option<usize>>,
+    // Powers of the root of unity
+    roots: Vec<Complex64>,
+}
+
+impl WeylBasis {
+    /// Labels in the same order as `gen_sylvester(d)`
+    pub fn new(d: usize) -> Self {
+        let mut labels = vec![(1 % d, 0), (0, 1 % d)];
+        for r in 1..d + 1 {
+            for s in 1..d + 1 {
+                if !((s == d && r == 1) || (s == 1 && r == d) || (s == d && r == d)) {
+                    labels.push((r % d, s % d));
+                }
+            }
+        }
+
+        let mut indices = vec![None; d * d];
+        for (i, (r, s)) in labels.iter().enumerate() {
+            indices[r * d + s] = Some(i);
+        }
+
+        let i1 = Complex64::new(0., 1.);
+        let roots = (0..d)
+            .map(|k| (2. * PI * i1 * k as f64 / d as f64).exp())
+            .collect();
+
+        WeylBasis {
+            d,
+            labels,
+            indices,
+            roots,
+        }
+    }
+
+    /// Number of basis elements, $d^2 - 1$
+    pub fn dim(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// The powers (r, s) of basis element `index`
+    pub fn label(&self, index: usize) -> (usize, usize) {
+        self.labels[index]
+    }
+
+    /// The basis element with powers (r, s), or None for the identity
+    pub fn index(&self, r: usize, s: usize) -> Option<usize> {
+        self.indices[(r % self.d) * self.d + s % self.d]
+    }
+
+    /// $\omega^k$
+    pub fn phase(&self, k: usize) -> Complex64 {
+        self.roots[k % self.d]
+    }
+
+    /// The matrix $X^r Z^s$ of basis element `index`
+    pub fn matrix(&self, index: usize) -> nd::Array2<Complex64> {
+        let (r, s) = self.label(index);
+        let mut m = nd::Array2::zeros((self.d, self.d));
+        for i in 0..self.d {
+            m[[(i + r) % self.d, i]] = self.phase(s * i);
+        }
+        m
+    }
+
+    ///
+    /// The product $T_a T_b = \phi T_c$, returned as $(\phi, c)$. $c$ is None when the product
+    /// is proportional to the identity.
+    ///
+    pub fn product(&self, a: usize, b: usize) -> (Complex64, Option<usize>) {
+        let (r, s) = self.label(a);
+        let (r_b, s_b) = self.label(b);
+        (self.phase(s * r_b), self.index(r + r_b, s + s_b))
+    }
+
+    ///
+    /// The commutator $[T_a, T_b] = (\omega^{s r'} - \omega^{r s'}) T_c$, returned as the
+    /// coefficient and c. None when the elements commute.
+    ///
+    pub fn commutator(&self, a: usize, b: usize) -> Option<(Complex64, usize)> {
+        let (r, s) = self.label(a);
+        let (r_b, s_b) = self.label(b);
+        if (s * r_b) % self.d == (r * s_b) % self.d {
+            return None;
+        }
+        let c = self.index(r + r_b, s + s_b)?;
+        Some((self.phase(s * r_b) - self.phase(r * s_b), c))
+    }
+
+    /// The structure constants of the basis, which agree with
+    /// `find_structure_constants(&gen_sylvester(d))`
+    pub fn structure_constants(&self) -> StructureConstants {
+        let mut f = StructureConstants::new(self.dim());
+        for a in 0..self.dim() {
+            for b in 0..self.dim() {
+                if let Some((value, c)) = self.commutator(a, b) {
+                    f.insert(a, b, c, value);
+                }
+            }
+        }
+        f
+    }
+}
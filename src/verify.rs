@@ -0,0 +1,254 @@
+use ndarray as nd;
+
+use ndarray_linalg::*;
+
+use rayon::prelude::*;
+
+use crate::lie_algebra::{Projection, StructureConstants};
+
+///
+/// The size of a residual for a pair of basis elements $(a, b)$
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct PairResidual {
+    pub a: usize,
+    pub b: usize,
+    pub residual: f64,
+}
+
+///
+/// The size of a residual for a triple of basis elements $(a, b, c)$
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct TripleResidual {
+    pub a: usize,
+    pub b: usize,
+    pub c: usize,
+    pub residual: f64,
+}
+
+///
+/// The size of a residual for a single basis element
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct ElementResidual {
+    pub a: usize,
+    pub residual: f64,
+}
+
+///
+/// The result of [`verify_lie_algebra`].
+///
+/// Residuals are kept for every pair and every element, while the Jacobi identity and
+/// antisymmetry only list the entries that are larger than `tolerance`.
+///
+#[derive(Clone, Debug)]
+pub struct LieAlgebraReport {
+    /// Number of basis elements
+    pub dim: usize,
+    /// Rank of the basis as a set of vectors
+    pub rank: usize,
+    /// Norm of the part of $[T_a, T_b]$ outside the span of the basis, for every $a < b$
+    pub closure: Vec<PairResidual>,
+    /// Norm of $\sum_e (f_{bcd} f_{ade} + f_{cad} f_{bde} + f_{abd} f_{cde})$ over $d$ and $e$,
+    /// for $a < b < c$
+    pub jacobi_violations: Vec<TripleResidual>,
+    /// $|f_{abc} + f_{bac}|$
+    pub antisymmetry_violations: Vec<TripleResidual>,
+    /// $|\mathrm{Tr}(T_a)|$
+    pub trace: Vec<ElementResidual>,
+    /// Frobenius norm of $T_a + T_a^\dagger$
+    pub anti_hermiticity: Vec<ElementResidual>,
+    pub tolerance: f64,
+}
+
+impl LieAlgebraReport {
+    pub fn is_independent(&self) -> bool {
+        self.rank == self.dim
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closure.iter().all(|x| x.residual < self.tolerance)
+    }
+
+    pub fn satisfies_jacobi(&self) -> bool {
+        self.jacobi_violations.is_empty()
+    }
+
+    pub fn is_antisymmetric(&self) -> bool {
+        self.antisymmetry_violations.is_empty()
+    }
+
+    pub fn is_traceless(&self) -> bool {
+        self.trace.iter().all(|x| x.residual < self.tolerance)
+    }
+
+    pub fn is_anti_hermitian(&self) -> bool {
+        self.anti_hermiticity
+            .iter()
+            .all(|x| x.residual < self.tolerance)
+    }
+
+    /// The basis is linearly independent, closed under the commutator, and its structure
+    /// constants satisfy antisymmetry and the Jacobi identity
+    pub fn is_lie_algebra(&self) -> bool {
+        self.is_independent()
+            && self.is_closed()
+            && self.satisfies_jacobi()
+            && self.is_antisymmetric()
+    }
+
+    /// The pair with the largest closure residual
+    pub fn worst_closure(&self) -> Option<&PairResidual> {
+        self.closure
+            .iter()
+            .max_by(|x, y| x.residual.total_cmp(&y.residual))
+    }
+}
+
+///
+/// Checks that a basis of matrices spans a Lie algebra, and reports how far every commutator,
+/// every structure constant and every element is from the requirements.
+///
+/// The structure constants are solved independently for every ordered pair $(a, b)$, so that
+/// their antisymmetry is a check rather than an assumption.
+///
+pub fn verify_lie_algebra(basis: &[nd::Array2<c64>]) -> LieAlgebraReport {
+    let tolerance = 1e-8;
+    let dim = basis.len();
+
+    // The zero algebra, which has nothing to check
+    if dim == 0 {
+        return LieAlgebraReport {
+            dim,
+            rank: 0,
+            closure: Vec::new(),
+            jacobi_violations: Vec::new(),
+            antisymmetry_violations: Vec::new(),
+            trace: Vec::new(),
+            anti_hermiticity: Vec::new(),
+            tolerance,
+        };
+    }
+
+    let commutator = |x: &nd::Array2<c64>, y: &nd::Array2<c64>| x.dot(y) - y.dot(x);
+
+    // Rewrite the basis as a matrix with each element mapped to a column
+    let columns: Vec<nd::Array1<c64>> = basis.iter().map(|x| x.iter().cloned().collect()).collect();
+    let columns: Vec<_> = columns.iter().map(|x| x.view()).collect();
+    let matrix = nd::stack(nd::Axis(1), columns.as_slice()).unwrap();
+
+    let (_, s, _) = matrix.svd(false, false).unwrap();
+    let s_max = s.iter().cloned().fold(0., f64::max);
+    let rank = s.iter().filter(|x| **x > tolerance * s_max.max(1.)).count();
+
+    // A dependent basis cannot be factorized, so every pair is solved by least squares instead
+    let projection = if rank == dim {
+        Some(Projection::new(basis.iter()))
+    } else {
+        None
+    };
+
+    let pairs: Vec<(usize, usize)> = (0..dim)
+        .flat_map(|a| (0..dim).filter(move |b| *b != a).map(move |b| (a, b)))
+        .collect();
+    let solutions: Vec<_> = pairs
+        .into_par_iter()
+        .map(|(a, b)| {
+            let c = commutator(&basis[a], &basis[b]);
+            let x = match &projection {
+                Some(projection) => projection.coordinates(&c),
+                None => {
+                    let c: nd::Array1<c64> = c.iter().cloned().collect();
+                    matrix.least_squares(&c).unwrap().solution
+                }
+            };
+            let c: nd::Array1<c64> = c.iter().cloned().collect();
+            let residual = (&c - &matrix.dot(&x)).norm_l2();
+            (a, b, x, residual)
+        })
+        .collect();
+
+    let mut f = StructureConstants::new(dim);
+    let mut closure = Vec::new();
+    for (a, b, x, residual) in solutions {
+        for (c, x_c) in x.iter().enumerate() {
+            if x_c.norm() > tolerance {
+                f.insert(a, b, c, *x_c);
+            }
+        }
+        if a < b {
+            closure.push(PairResidual { a, b, residual });
+        }
+    }
+
+    let mut antisymmetry_violations = Vec::new();
+    for a in 0..dim {
+        for b in a + 1..dim {
+            for c in 0..dim {
+                let residual = (f.get(a, b, c) + f.get(b, a, c)).norm();
+                if residual > tolerance {
+                    antisymmetry_violations.push(TripleResidual { a, b, c, residual });
+                }
+            }
+        }
+    }
+
+    let triples: Vec<(usize, usize, usize)> = (0..dim)
+        .flat_map(|a| (a + 1..dim).flat_map(move |b| (b + 1..dim).map(move |c| (a, b, c))))
+        .collect();
+    let jacobi_violations = triples
+        .into_par_iter()
+        .filter_map(|(a, b, c)| {
+            let residual = jacobi(&f, a, b, c);
+            if residual > tolerance {
+                Some(TripleResidual { a, b, c, residual })
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    let trace = basis
+        .iter()
+        .enumerate()
+        .map(|(a, x)| ElementResidual {
+            a,
+            residual: x.diag().sum().norm(),
+        })
+        .collect();
+
+    let anti_hermiticity = basis
+        .iter()
+        .enumerate()
+        .map(|(a, x)| ElementResidual {
+            a,
+            residual: (x + &x.t().mapv(|x| x.conj())).norm_l2(),
+        })
+        .collect();
+
+    LieAlgebraReport {
+        dim,
+        rank,
+        closure,
+        jacobi_violations,
+        antisymmetry_violations,
+        trace,
+        anti_hermiticity,
+        tolerance,
+    }
+}
+
+// Norm of [T_a, [T_b, T_c]] + [T_b, [T_c, T_a]] + [T_c, [T_a, T_b]] in terms of the structure
+// constants
+fn jacobi(f: &StructureConstants, a: usize, b: usize, c: usize) -> f64 {
+    let mut res = vec![c64::new(0., 0.); f.dim()];
+    for &(x, y, z) in [(a, b, c), (b, c, a), (c, a, b)].iter() {
+        for (d, f_yzd) in f.pair(y, z) {
+            for (e, f_xde) in f.pair(x, *d) {
+                res[*e] += f_yzd * f_xde;
+            }
+        }
+    }
+    res.iter().map(|x| x.norm_sqr()).sum::<f64>().sqrt()
+}
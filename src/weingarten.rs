@@ -0,0 +1,501 @@
+use ndarray as nd;
+
+use ndarray_linalg::*;
+
+use itertools::Itertools;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, ToPrimitive, Zero};
+
+use std::collections::HashMap;
+
+///
+/// The lengths of the cycles of a permutation, in decreasing order. The permutation maps `a` to
+/// `sigma[a]`.
+///
+pub fn cycle_type(sigma: &[usize]) -> Vec<usize> {
+    let mut seen = vec![false; sigma.len()];
+    let mut res = vec![];
+    for start in 0..sigma.len() {
+        let mut len = 0;
+        let mut a = start;
+        while !seen[a] {
+            seen[a] = true;
+            a = sigma[a];
+            len += 1;
+        }
+        if len > 0 {
+            res.push(len);
+        }
+    }
+    res.sort_unstable_by(|a, b| b.cmp(a));
+    res
+}
+
+///
+/// The partitions of k, in decreasing lexicographic order
+///
+pub fn partitions(k: usize) -> Vec<Vec<usize>> {
+    fn fill(k: usize, max: usize, prefix: &mut Vec<usize>, res: &mut Vec<Vec<usize>>) {
+        if k == 0 {
+            res.push(prefix.clone());
+            return;
+        }
+        for part in (1..=k.min(max)).rev() {
+            prefix.push(part);
+            fill(k - part, part, prefix, res);
+            prefix.pop();
+        }
+    }
+    let mut res = vec![];
+    fill(k, k, &mut vec![], &mut res);
+    res
+}
+
+///
+/// The character $\chi^\lambda(\mu)$ of the irreducible representation $\lambda$ of the
+/// symmetric group on the permutations of cycle type $\mu$, by the Murnaghan–Nakayama rule.
+///
+/// The rim hooks are removed on the beta numbers $\beta_i = \lambda_i + \ell - 1 - i$: removing a
+/// hook of length r replaces some $\beta$ by $\beta - r$, with the sign
+/// $(-1)^{\#\{\beta' : \beta - r < \beta' < \beta\}}$.
+///
+pub fn character(lambda: &[usize], mu: &[usize]) -> i64 {
+    assert_eq!(
+        lambda.iter().sum::<usize>(),
+        mu.iter().sum::<usize>(),
+        "The partitions do not have the same size"
+    );
+    let l = lambda.len();
+    let beta: Vec<usize> = lambda
+        .iter()
+        .enumerate()
+        .map(|(i, x)| x + l - 1 - i)
+        .collect();
+    beta_character(&beta, mu)
+}
+
+fn beta_character(beta: &[usize], mu: &[usize]) -> i64 {
+    let (r, rest) = match mu.split_first() {
+        Some(x) => x,
+        None => return 1,
+    };
+    let mut res = 0;
+    for (i, b) in beta.iter().enumerate() {
+        if *b < *r || beta.contains(&(b - r)) {
+            continue;
+        }
+        let between = beta.iter().filter(|x| **x > b - r && *x < b).count();
+        let sign = if between % 2 == 0 { 1 } else { -1 };
+        let mut removed = beta.to_vec();
+        removed[i] = b - r;
+        res += sign * beta_character(&removed, rest);
+    }
+    res
+}
+
+///
+/// The unitary Weingarten function $\mathrm{Wg}^U(\sigma, d)$, as an exact rational.
+///
+/// It is computed from the characters of the symmetric group $S_k$,
+/// $$\mathrm{Wg}^U(\sigma, d) = \frac{1}{k!} \sum_{\lambda \vdash k, \ell(\lambda) \leq d}
+/// \frac{\chi^\lambda(1) \chi^\lambda(\sigma)}{\prod_{(i, j) \in \lambda} (d + j - i)}$$
+/// which is also valid for $d < k$, where the Gram matrix of the permutations is singular and
+/// $\mathrm{Wg}^U$ is its pseudo-inverse.
+///
+pub fn weingarten_unitary(sigma: &[usize], d: usize) -> BigRational {
+    weingarten_unitary_cycle_type(&cycle_type(sigma), d)
+}
+
+fn weingarten_unitary_cycle_type(mu: &[usize], d: usize) -> BigRational {
+    let k: usize = mu.iter().sum();
+    let identity = vec![1; k];
+    let mut res = BigRational::zero();
+    for lambda in partitions(k) {
+        // The product of d + content vanishes exactly when lambda has more than d rows
+        let mut contents = BigInt::one();
+        for (i, row) in lambda.iter().enumerate() {
+            for j in 0..*row {
+                contents *= BigInt::from(d as i64 + j as i64 - i as i64);
+            }
+        }
+        if contents.is_zero() {
+            continue;
+        }
+        let dim = character(&lambda, &identity);
+        let chi = character(&lambda, mu);
+        res += BigRational::new(BigInt::from(dim * chi), contents);
+    }
+    res / BigRational::from_integer(factorial(k))
+}
+
+fn factorial(k: usize) -> BigInt {
+    (1..=k).fold(BigInt::one(), |acc, x| acc * BigInt::from(x))
+}
+
+///
+/// The Haar integral over U(d)
+/// $$\int U_{i_1 j_1} \cdots U_{i_k j_k} \overline{U_{i'_1 j'_1}} \cdots
+/// \overline{U_{i'_k j'_k}} dU = \sum_{\sigma, \tau \in S_k} \prod_a \delta_{i_a
+/// i'_{\sigma(a)}} \delta_{j_a j'_{\tau(a)}} \mathrm{Wg}^U(\sigma^{-1} \tau, d)$$
+/// as an exact rational. The integral vanishes unless there are as many $U$ as $\bar{U}$.
+///
+pub fn unitary_moment(
+    i: &[usize],
+    j: &[usize],
+    i_conj: &[usize],
+    j_conj: &[usize],
+    d: usize,
+) -> BigRational {
+    let k = i.len();
+    assert_eq!(j.len(), k, "Every entry needs a row and a column index");
+    assert_eq!(
+        j_conj.len(),
+        i_conj.len(),
+        "Every entry needs a row and a column index"
+    );
+    if i_conj.len() != k {
+        return BigRational::zero();
+    }
+
+    let matches = |x: &[usize], y: &[usize], sigma: &[usize]| (0..k).all(|a| x[a] == y[sigma[a]]);
+    let rows: Vec<Vec<usize>> = (0..k)
+        .permutations(k)
+        .filter(|sigma| matches(i, i_conj, sigma))
+        .collect();
+    let columns: Vec<Vec<usize>> = (0..k)
+        .permutations(k)
+        .filter(|tau| matches(j, j_conj, tau))
+        .collect();
+
+    let mut cache: HashMap<Vec<usize>, BigRational> = HashMap::new();
+    let mut res = BigRational::zero();
+    for sigma in rows.iter() {
+        let mut inverse = vec![0; k];
+        for (a, s) in sigma.iter().enumerate() {
+            inverse[*s] = a;
+        }
+        for tau in columns.iter() {
+            let product: Vec<usize> = tau.iter().map(|t| inverse[*t]).collect();
+            let mu = cycle_type(&product);
+            let wg = cache
+                .entry(mu)
+                .or_insert_with_key(|mu| weingarten_unitary_cycle_type(mu, d));
+            res += wg.clone();
+        }
+    }
+    res
+}
+
+///
+/// The pair partitions of $\{0, \ldots, n - 1\}$, with the pairs sorted by their first element
+///
+pub fn pair_partitions(n: usize) -> Vec<Vec<(usize, usize)>> {
+    fn fill(rest: &[usize], prefix: &mut Vec<(usize, usize)>, res: &mut Vec<Vec<(usize, usize)>>) {
+        let (first, rest) = match rest.split_first() {
+            Some(x) => x,
+            None => {
+                res.push(prefix.clone());
+                return;
+            }
+        };
+        for (k, second) in rest.iter().enumerate() {
+            let mut remaining = rest.to_vec();
+            remaining.remove(k);
+            prefix.push((*first, *second));
+            fill(&remaining, prefix, res);
+            prefix.pop();
+        }
+    }
+    if n % 2 == 1 {
+        return vec![];
+    }
+    let mut res = vec![];
+    fill(&(0..n).collect::<Vec<_>>(), &mut vec![], &mut res);
+    res
+}
+
+// The number of loops of the graph whose edges are the pairs of both pair partitions
+fn loops(m: &[(usize, usize)], n: &[(usize, usize)]) -> usize {
+    let size = 2 * m.len();
+    let partner = |pairs: &[(usize, usize)]| {
+        let mut res = vec![0; size];
+        for (a, b) in pairs.iter() {
+            res[*a] = *b;
+            res[*b] = *a;
+        }
+        res
+    };
+    let (pm, pn) = (partner(m), partner(n));
+    let mut seen = vec![false; size];
+    let mut res = 0;
+    for start in 0..size {
+        if seen[start] {
+            continue;
+        }
+        res += 1;
+        let mut a = start;
+        loop {
+            seen[a] = true;
+            let b = pm[a];
+            seen[b] = true;
+            a = pn[b];
+            if a == start {
+                break;
+            }
+        }
+    }
+    res
+}
+
+///
+/// The orthogonal Weingarten matrix $\mathrm{Wg}^O(m, n, d)$ for the pair partitions $m, n$ of
+/// `pair_partitions(2k)`, as exact rationals.
+///
+/// It is the pseudo-inverse of the Gram matrix $G(m, n) = d^{\mathrm{loops}(m, n)}$, which is
+/// singular when $d < k$. The pseudo-inverse is computed exactly from a rank factorization
+/// $G = B F$, as $G^+ = F^T (F F^T)^{-1} (B^T B)^{-1} B^T$.
+///
+pub fn weingarten_orthogonal(k: usize, d: usize) -> Vec<Vec<BigRational>> {
+    let matchings = pair_partitions(2 * k);
+    let gram: Vec<Vec<BigRational>> = matchings
+        .iter()
+        .map(|m| {
+            matchings
+                .iter()
+                .map(|n| BigRational::from_integer(BigInt::from(d).pow(loops(m, n) as u32)))
+                .collect()
+        })
+        .collect();
+    pseudo_inverse(&gram)
+}
+
+///
+/// The Haar integral over O(d)
+/// $$\int O_{i_1 j_1} \cdots O_{i_{2k} j_{2k}} dO = \sum_{m, n} \Delta_m(i) \Delta_n(j)
+/// \mathrm{Wg}^O(m, n, d)$$
+/// as an exact rational, where $\Delta_m(i)$ is 1 when $i_a = i_b$ for every pair $(a, b)$ of
+/// $m$. The integral vanishes for an odd number of entries.
+///
+/// The Weingarten matrix is computed on every call. To evaluate many moments of the same degree,
+/// compute it once with [`weingarten_orthogonal`] and use [`orthogonal_moment_from_weingarten`].
+///
+pub fn orthogonal_moment(i: &[usize], j: &[usize], d: usize) -> BigRational {
+    let wg = if i.len() % 2 == 0 {
+        weingarten_orthogonal(i.len() / 2, d)
+    } else {
+        vec![]
+    };
+    orthogonal_moment_from_weingarten(i, j, &wg)
+}
+
+///
+/// [`orthogonal_moment`] with the matrix `wg` = `weingarten_orthogonal(k, d)` for $2k$ entries
+///
+pub fn orthogonal_moment_from_weingarten(
+    i: &[usize],
+    j: &[usize],
+    wg: &[Vec<BigRational>],
+) -> BigRational {
+    assert_eq!(
+        i.len(),
+        j.len(),
+        "Every entry needs a row and a column index"
+    );
+    if i.len() % 2 == 1 {
+        return BigRational::zero();
+    }
+    let matchings = pair_partitions(i.len());
+    assert_eq!(
+        wg.len(),
+        matchings.len(),
+        "The Weingarten matrix does not match the number of entries"
+    );
+    let delta = |x: &[usize], m: &[(usize, usize)]| m.iter().all(|(a, b)| x[*a] == x[*b]);
+    let rows: Vec<usize> = (0..matchings.len())
+        .filter(|m| delta(i, &matchings[*m]))
+        .collect();
+    let columns: Vec<usize> = (0..matchings.len())
+        .filter(|n| delta(j, &matchings[*n]))
+        .collect();
+    let mut res = BigRational::zero();
+    for m in rows.iter() {
+        for n in columns.iter() {
+            res += wg[*m][*n].clone();
+        }
+    }
+    res
+}
+
+///
+/// The twirl $\int U^{\otimes k} X U^{\dagger \otimes k} dU$ over U(d) of an operator on
+/// $(\mathbb{C}^d)^{\otimes k}$,
+/// $$\sum_{\sigma, \tau \in S_k} \mathrm{Wg}^U(\sigma^{-1} \tau, d)
+/// \mathrm{Tr}(P_\sigma^\dagger X) P_\tau$$
+/// where $P_\sigma$ permutes the tensor factors. For example, the twirl of $T_a \otimes T_b$ for
+/// the Gell-Mann matrices of `gen_gellmann` only depends on $\mathrm{Tr}(T_a T_b)$.
+///
+pub fn twirl(x: &nd::Array2<c64>, d: usize, k: usize) -> nd::Array2<c64> {
+    let size = d.pow(k as u32);
+    assert_eq!(
+        x.shape(),
+        &[size, size],
+        "The operator does not act on k copies of C^d"
+    );
+
+    let permutations: Vec<Vec<usize>> = (0..k).permutations(k).collect();
+    let operators: Vec<nd::Array2<c64>> = permutations
+        .iter()
+        .map(|sigma| permutation_operator(sigma, d))
+        .collect();
+    let overlaps: Vec<c64> = operators
+        .iter()
+        .map(|p| (p.mapv(|x| x.conj()) * x).sum())
+        .collect();
+
+    // Wg only depends on the cycle type, of which there are far fewer than the k!^2 pairs
+    let mut cache: HashMap<Vec<usize>, f64> = HashMap::new();
+    let mut res = nd::Array2::zeros((size, size));
+    for (sigma, overlap) in permutations.iter().zip(overlaps.iter()) {
+        let mut inverse = vec![0; k];
+        for (a, s) in sigma.iter().enumerate() {
+            inverse[*s] = a;
+        }
+        for (tau, p_tau) in permutations.iter().zip(operators.iter()) {
+            let product: Vec<usize> = tau.iter().map(|t| inverse[*t]).collect();
+            let wg = *cache
+                .entry(cycle_type(&product))
+                .or_insert_with_key(|mu| weingarten_unitary_cycle_type(mu, d).to_f64().unwrap());
+            res.scaled_add(overlap * wg, p_tau);
+        }
+    }
+    res
+}
+
+// The operator sending the tensor factor a to the factor sigma(a)
+fn permutation_operator(sigma: &[usize], d: usize) -> nd::Array2<c64> {
+    let k = sigma.len();
+    let size = d.pow(k as u32);
+    let digits = |mut x: usize| {
+        let mut res = vec![0; k];
+        for a in (0..k).rev() {
+            res[a] = x % d;
+            x /= d;
+        }
+        res
+    };
+    let mut res = nd::Array2::zeros((size, size));
+    for col in 0..size {
+        let input = digits(col);
+        let mut output = vec![0; k];
+        for a in 0..k {
+            output[sigma[a]] = input[a];
+        }
+        let row = output.iter().fold(0, |acc, x| acc * d + x);
+        res[[row, col]] = c64::new(1., 0.);
+    }
+    res
+}
+
+type Matrix = Vec<Vec<BigRational>>;
+
+fn transpose(a: &[Vec<BigRational>]) -> Matrix {
+    let cols = a.first().map_or(0, |x| x.len());
+    (0..cols)
+        .map(|j| a.iter().map(|row| row[j].clone()).collect())
+        .collect()
+}
+
+fn product(a: &[Vec<BigRational>], b: &[Vec<BigRational>]) -> Matrix {
+    let cols = b.first().map_or(0, |x| x.len());
+    a.iter()
+        .map(|row| {
+            (0..cols)
+                .map(|j| {
+                    row.iter()
+                        .zip(b.iter())
+                        .filter(|(x, _)| !x.is_zero())
+                        .fold(BigRational::zero(), |acc, (x, b_row)| acc + x * &b_row[j])
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// The reduced row echelon form, with the columns of the pivots
+fn row_echelon(mut a: Matrix) -> (Matrix, Vec<usize>) {
+    let cols = a.first().map_or(0, |x| x.len());
+    let mut pivots = vec![];
+    for col in 0..cols {
+        let row = pivots.len();
+        let best = match (row..a.len()).find(|i| !a[*i][col].is_zero()) {
+            Some(x) => x,
+            None => continue,
+        };
+        a.swap(row, best);
+        let pivot = a[row][col].clone();
+        for x in a[row].iter_mut() {
+            *x /= pivot.clone();
+        }
+        let pivot_row = a[row].clone();
+        for (i, other) in a.iter_mut().enumerate() {
+            if i == row || other[col].is_zero() {
+                continue;
+            }
+            let factor = other[col].clone();
+            for (x, y) in other.iter_mut().zip(pivot_row.iter()) {
+                *x -= &factor * y;
+            }
+        }
+        pivots.push(col);
+        if pivots.len() == a.len() {
+            break;
+        }
+    }
+    a.truncate(pivots.len());
+    (a, pivots)
+}
+
+fn inverse(a: &[Vec<BigRational>]) -> Matrix {
+    let n = a.len();
+    let augmented: Matrix = a
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let mut row = row.clone();
+            row.extend((0..n).map(|j| {
+                if i == j {
+                    BigRational::one()
+                } else {
+                    BigRational::zero()
+                }
+            }));
+            row
+        })
+        .collect();
+    let (reduced, pivots) = row_echelon(augmented);
+    assert!(
+        pivots.len() == n && pivots[n - 1] < n,
+        "The matrix is singular"
+    );
+    reduced.into_iter().map(|row| row[n..].to_vec()).collect()
+}
+
+fn pseudo_inverse(a: &[Vec<BigRational>]) -> Matrix {
+    let (f, pivots) = row_echelon(a.to_vec());
+    if pivots.is_empty() {
+        return a
+            .iter()
+            .map(|row| vec![BigRational::zero(); row.len()])
+            .collect();
+    }
+    let b: Matrix = a
+        .iter()
+        .map(|row| pivots.iter().map(|j| row[*j].clone()).collect())
+        .collect();
+    let f_t = transpose(&f);
+    let b_t = transpose(&b);
+    let left = product(&f_t, &inverse(&product(&f, &f_t)));
+    let right = product(&inverse(&product(&b_t, &b)), &b_t);
+    product(&left, &right)
+}
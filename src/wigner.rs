@@ -0,0 +1,68 @@
+use ndarray as nd;
+
+use ndarray_linalg::*;
+
+use crate::su2::s_x;
+
+///
+/// The Wigner small-d matrix $d^j(\beta) = e^{-i \beta J_y}$ of spin $j$, integer or half-integer,
+/// with the rows and columns in the ordering of `su2::s_z(j)`, from $m = j$ down to $m = -j$.
+///
+/// The matrix is computed with the Fourier method instead of the factorials of the Wigner
+/// formula, which lose all precision for large $j$. The rotation $e^{-i \frac{\pi}{2} J_z}$ takes
+/// $J_x$ to $J_y$, so with the real eigenvectors $w_\mu$ of the tridiagonal $J_x$
+/// $$d^j_{m' m}(\beta) = i^{m - m'} \sum_{\mu = -j}^{j} e^{-i \mu \beta} (w_\mu)_{m'} (w_\mu)_m$$
+/// where the eigenvalues $\mu$ are exact and the eigenvectors are well conditioned, as the
+/// eigenvalues are one apart.
+///
+pub fn wigner_small_d(j: f64, beta: f64) -> nd::Array2<f64> {
+    let n = dimension(j);
+    let w = jx_eigenvectors(j);
+
+    // Eigenvalues of J_x in ascending order, as returned by eigh, so that the sum is
+    // W diag(e^{-i mu beta}) W^T, with real W
+    let angles = nd::Array1::from_shape_fn(n, |k| -(k as f64 - j) * beta);
+    let re = (&w * &angles.mapv(f64::cos)).dot(&w.t());
+    let im = (&w * &angles.mapv(f64::sin)).dot(&w.t());
+
+    // i^{m - m'} with m' = j - a and m = j - b, where d is real
+    nd::Array2::from_shape_fn((n, n), |(a, b)| match (a + 4 * n - b) % 4 {
+        0 => re[[a, b]],
+        1 => -im[[a, b]],
+        2 => -re[[a, b]],
+        _ => im[[a, b]],
+    })
+}
+
+///
+/// The Wigner D-matrix of spin $j$ for the Euler angles $(\alpha, \beta, \gamma)$ in the ZYZ
+/// convention,
+/// $$D^j(\alpha, \beta, \gamma) = e^{-i \alpha J_z} e^{-i \beta J_y} e^{-i \gamma J_z}, \quad
+/// D^j_{m' m} = e^{-i m' \alpha} d^j_{m' m}(\beta) e^{-i m \gamma}$$
+/// in the ordering of `su2::s_z(j)`. For $j = 1/2$ this is `euler::su2_from_euler` with
+/// `EulerConvention::Zyz`.
+///
+pub fn wigner_d(j: f64, alpha: f64, beta: f64, gamma: f64) -> nd::Array2<c64> {
+    let n = dimension(j);
+    let d = wigner_small_d(j, beta);
+    let m = |a: usize| j - a as f64;
+
+    nd::Array2::from_shape_fn((n, n), |(a, b)| {
+        c64::from_polar(d[[a, b]], -m(a) * alpha - m(b) * gamma)
+    })
+}
+
+// The dimension 2j + 1, checking that j is a non-negative multiple of 1/2
+fn dimension(j: f64) -> usize {
+    assert!(
+        j >= 0. && (2. * j).fract() == 0.,
+        "The spin is a non-negative integer or half-integer"
+    );
+    (j * 2. + 1.) as usize
+}
+
+// The orthonormal eigenvectors of J_x as columns, for the eigenvalues -j, ..., j
+fn jx_eigenvectors(j: f64) -> nd::Array2<f64> {
+    let (_, w) = s_x(j).eigh(UPLO::Upper).unwrap();
+    w
+}
@@ -0,0 +1,77 @@
+use approx::assert_abs_diff_eq;
+use lie::adjoint::*;
+use lie::gellmann::*;
+use lie::lie_algebra::*;
+use ndarray as nd;
+use num_complex::Complex64;
+
+// A unitary from a permutation of the basis states and diagonal phases
+fn unitary(phases: &[f64], permutation: &[usize]) -> nd::Array2<Complex64> {
+    let n = phases.len();
+    let mut u = nd::Array2::zeros((n, n));
+    for (i, (phase, j)) in phases.iter().zip(permutation).enumerate() {
+        u[[*j, i]] = Complex64::new(0., *phase).exp();
+    }
+    u
+}
+
+fn coords(x: &[f64]) -> nd::Array1<Complex64> {
+    x.iter().map(|x| Complex64::new(*x, 0.)).collect()
+}
+
+#[test]
+fn test_adjoint_representation_is_homomorphism() {
+    let mut basis = gen_gellmann(3);
+    basis[3] = &basis[3] + &basis[1];
+    let algebra = LieAlgebra::new(basis);
+    let ad = adjoint_representation(&algebra);
+    let f = algebra.structure_constants();
+
+    for (a, ad_a) in ad.iter().enumerate() {
+        for (b, ad_b) in ad.iter().enumerate() {
+            let mut expected = nd::Array2::zeros(ad_a.raw_dim());
+            for (c, f_abc) in f.pair(a, b) {
+                expected.scaled_add(*f_abc, &ad[*c]);
+            }
+            assert_abs_diff_eq!(ad_a.dot(ad_b) - ad_b.dot(ad_a), expected, epsilon = 1e-8);
+        }
+    }
+
+    // ad_x y = [x, y]
+    let x = coords(&[0.3, -1., 0.2, 0.5, 0., 0.1, 2., -0.4]);
+    let y = coords(&[1., 0.2, 0., -0.7, 0.3, 0.6, -0.1, 0.9]);
+    let mut ad_x = nd::Array2::zeros(ad[0].raw_dim());
+    for (x_a, ad_a) in x.iter().zip(&ad) {
+        ad_x.scaled_add(*x_a, ad_a);
+    }
+    assert_abs_diff_eq!(ad_x.dot(&y), algebra.bracket(&x, &y), epsilon = 1e-8);
+}
+
+#[test]
+fn test_adjoint_action_is_orthogonal() {
+    let algebra = LieAlgebra::new(gen_gellmann(3));
+    let u = unitary(&[0.3, -1.2, 2.], &[1, 2, 0]);
+    let v = unitary(&[0.7, 0.1, -0.5], &[0, 2, 1]);
+
+    let ad_u = adjoint_action(&algebra, &u);
+    assert!(ad_u.iter().all(|x| x.im.abs() < 1e-8));
+    assert_abs_diff_eq!(ad_u.t().dot(&ad_u), nd::Array2::eye(8), epsilon = 1e-8);
+
+    // Ad is a group homomorphism
+    let ad_v = adjoint_action(&algebra, &v);
+    assert_abs_diff_eq!(
+        adjoint_action(&algebra, &u.dot(&v)),
+        ad_u.dot(&ad_v),
+        epsilon = 1e-8
+    );
+
+    // Ad(U) acts on coordinates like conjugation on matrices
+    let x = coords(&[0.3, -1., 0.2, 0.5, 0., 0.1, 2., -0.4]);
+    let m = algebra.from_coordinates(&x);
+    let u_dagger = u.t().mapv(|x| x.conj());
+    assert_abs_diff_eq!(
+        ad_u.dot(&x),
+        algebra.to_coordinates(&u.dot(&m).dot(&u_dagger)),
+        epsilon = 1e-8
+    );
+}
@@ -0,0 +1,98 @@
+use approx::assert_abs_diff_eq;
+use lie::bch::*;
+use lie::exponential::*;
+use lie::gellmann::*;
+use lie::lie_algebra::*;
+use ndarray as nd;
+use num_complex::Complex64;
+
+fn element(dim: usize, scale: f64, phase: f64) -> nd::Array1<Complex64> {
+    nd::Array1::from_shape_fn(dim, |a| {
+        Complex64::new(scale * (0.91 * a as f64 + phase).sin(), 0.)
+    })
+}
+
+fn distance(x: &nd::Array1<Complex64>, y: &nd::Array1<Complex64>) -> f64 {
+    (x - y).iter().map(|x| x.norm_sqr()).sum::<f64>().sqrt()
+}
+
+#[test]
+fn test_bch_terms() {
+    let algebra = LieAlgebra::new(gen_gellmann(3));
+    let f = algebra.structure_constants();
+    let x = element(8, 0.3, 0.2);
+    let y = element(8, 0.3, 1.7);
+    let b = |x: &nd::Array1<Complex64>, y: &nd::Array1<Complex64>| algebra.bracket(x, y);
+
+    let z = bch(&x, &y, 4, f);
+    assert_eq!(z.terms.len(), 4);
+    assert_eq!(z.errors.len(), 4);
+    assert_abs_diff_eq!(z.terms[0], &x + &y, epsilon = 1e-14);
+    assert_abs_diff_eq!(
+        z.terms[1],
+        b(&x, &y) * Complex64::new(0.5, 0.),
+        epsilon = 1e-14
+    );
+    let z3 = (b(&x, &b(&x, &y)) + b(&y, &b(&y, &x))) / Complex64::new(12., 0.);
+    assert_abs_diff_eq!(z.terms[2], z3, epsilon = 1e-14);
+    let z4 = b(&y, &b(&x, &b(&x, &y))) / Complex64::new(-24., 0.);
+    assert_abs_diff_eq!(z.terms[3], z4, epsilon = 1e-14);
+}
+
+#[test]
+fn test_bch_convergence() {
+    let algebra = LieAlgebra::new(gen_gellmann(3));
+    let x = element(8, 0.2, 0.2);
+    let y = element(8, 0.2, 1.7);
+    let product = exp_map(&algebra, &x).dot(&exp_map(&algebra, &y));
+    let exact = log_map(&algebra, &product).unwrap().coords;
+
+    let z = bch(&x, &y, 14, algebra.structure_constants());
+    let mut truncated = nd::Array1::zeros(8);
+    for (n, term) in z.terms.iter().enumerate() {
+        truncated += term;
+        let error = distance(&truncated, &exact);
+        // The leading omitted term dominates the error
+        assert!(error < 2. * z.errors[n] + 1e-12);
+        if n > 0 {
+            assert!(z.errors[n] < z.errors[n - 1]);
+        }
+    }
+    assert!(distance(&z.sum(), &exact) < 1e-10);
+}
+
+#[test]
+fn test_zassenhaus() {
+    let algebra = LieAlgebra::new(gen_gellmann(3));
+    let f = algebra.structure_constants();
+    let x = element(8, 0.1, 0.4);
+    let y = element(8, 0.1, 2.3);
+    let b = |x: &nd::Array1<Complex64>, y: &nd::Array1<Complex64>| algebra.bracket(x, y);
+
+    let c = zassenhaus(&x, &y, 10, f);
+    assert_eq!(c.terms.len(), 9);
+    assert_abs_diff_eq!(
+        c.terms[0],
+        b(&x, &y) * Complex64::new(-0.5, 0.),
+        epsilon = 1e-14
+    );
+    let c3 =
+        b(&y, &b(&x, &y)) / Complex64::new(3., 0.) + b(&x, &b(&x, &y)) / Complex64::new(6., 0.);
+    assert_abs_diff_eq!(c.terms[1], c3, epsilon = 1e-14);
+
+    // e^{X + Y} against the product of the factors
+    let exact = exp_map(&algebra, &(&x + &y));
+    let mut product = exp_map(&algebra, &x).dot(&exp_map(&algebra, &y));
+    for (n, term) in c.terms.iter().enumerate() {
+        product = product.dot(&exp_map(&algebra, term));
+        let log = log_map(&algebra, &product.t().mapv(|x| x.conj()).dot(&exact))
+            .unwrap()
+            .coords;
+        let error = log.iter().map(|x| x.norm_sqr()).sum::<f64>().sqrt();
+        // The factors do not decrease monotonically, so the estimate only holds at low orders
+        if n < 6 {
+            assert!(error < 2. * c.errors[n + 1]);
+        }
+    }
+    assert_abs_diff_eq!(product, exact, epsilon = 1e-8);
+}
@@ -0,0 +1,87 @@
+use approx::assert_abs_diff_eq;
+use lie::adjoint::*;
+use lie::casimir::*;
+use lie::gellmann::*;
+use lie::lie_algebra::*;
+use lie::su2::*;
+use ndarray as nd;
+use num_complex::Complex64;
+
+// The spin j representation of the basis (i sigma_z, i sigma_y, i sigma_x) of gen_gellmann(2)
+fn spin_representation(j: f64) -> Vec<nd::Array2<Complex64>> {
+    let i = Complex64::new(0., 1.);
+    let [s_z, s_x, s_y] = gen_su2(j);
+
+    // gen_su2 returns the real matrix i S_y
+    vec![s_z * (2. * i), s_y * 2., s_x * (2. * i)]
+}
+
+#[test]
+fn test_su2_quadratic_casimir() {
+    let algebra = LieAlgebra::new(gen_gellmann(2));
+
+    for &j in [0.5, 1., 1.5, 2.].iter() {
+        let rep = spin_representation(j);
+
+        let c2 = casimir_in_representation(&algebra, &rep, 2);
+        let eigenvalue = c2.eigenvalue.unwrap();
+        assert_abs_diff_eq!(eigenvalue.re, j * (j + 1.) / 2., epsilon = 1e-8);
+        assert_abs_diff_eq!(eigenvalue.im, 0., epsilon = 1e-8);
+
+        // su(2) has no cubic Casimir
+        let c3 = casimir_in_representation(&algebra, &rep, 3);
+        assert_abs_diff_eq!(c3.eigenvalue.unwrap().norm(), 0., epsilon = 1e-8);
+    }
+}
+
+#[test]
+fn test_reducible_representation() {
+    let algebra = LieAlgebra::new(gen_gellmann(2));
+
+    // The direct sum of spin 1/2 and spin 1
+    let rep: Vec<_> = spin_representation(0.5)
+        .iter()
+        .zip(spin_representation(1.).iter())
+        .map(|(x, y)| {
+            let mut m = nd::Array2::zeros((5, 5));
+            m.slice_mut(nd::s![..2, ..2]).assign(x);
+            m.slice_mut(nd::s![2.., 2..]).assign(y);
+            m
+        })
+        .collect();
+
+    assert!(casimir_in_representation(&algebra, &rep, 2)
+        .eigenvalue
+        .is_none());
+}
+
+#[test]
+fn test_su3_casimirs() {
+    let algebra = LieAlgebra::new(gen_gellmann(3));
+
+    // With the Killing form -12 delta_ab, C_2 = 1/12 sum lambda_a^2 = 4/9
+    let c2 = casimir(&algebra, 2).eigenvalue.unwrap();
+    assert_abs_diff_eq!(c2.re, 4. / 9., epsilon = 1e-8);
+
+    // The adjoint representation has C_2 = 1 with the Killing normalization
+    let adjoint = adjoint_representation(&algebra);
+    let c2 = casimir_in_representation(&algebra, &adjoint, 2);
+    assert_abs_diff_eq!(c2.eigenvalue.unwrap().re, 1., epsilon = 1e-8);
+
+    // The cubic Casimir separates the fundamental and antifundamental representations
+    let conjugate: Vec<_> = algebra
+        .basis()
+        .iter()
+        .map(|x| x.mapv(|x| x.conj()))
+        .collect();
+    let c3 = casimir(&algebra, 3).eigenvalue.unwrap();
+    let c3_conjugate = casimir_in_representation(&algebra, &conjugate, 3)
+        .eigenvalue
+        .unwrap();
+    assert!(c3.norm() > 1e-4);
+    assert_abs_diff_eq!(c3, -c3_conjugate, epsilon = 1e-8);
+
+    // Higher Casimirs are proportional to the identity as well
+    assert!(casimir(&algebra, 4).eigenvalue.is_some());
+    assert!(casimir(&algebra, 5).eigenvalue.is_some());
+}
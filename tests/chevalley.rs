@@ -0,0 +1,255 @@
+use approx::assert_abs_diff_eq;
+use lie::chevalley::*;
+use lie::classify::*;
+use lie::lie_algebra::*;
+use lie::verify::*;
+
+use std::collections::HashMap;
+
+fn all_types() -> Vec<CartanType> {
+    let mut types = Vec::new();
+    for n in 1..=5 {
+        types.push(CartanType::new('A', n).unwrap());
+    }
+    for n in 2..=5 {
+        types.push(CartanType::new('B', n).unwrap());
+    }
+    for n in 3..=5 {
+        types.push(CartanType::new('C', n).unwrap());
+    }
+    for n in 4..=6 {
+        types.push(CartanType::new('D', n).unwrap());
+    }
+    for n in 6..=8 {
+        types.push(CartanType::new('E', n).unwrap());
+    }
+    types.push(CartanType::new('F', 4).unwrap());
+    types.push(CartanType::new('G', 2).unwrap());
+    types
+}
+
+// [[x_a, x_b], x_c] + cyclic = 0, in exact integers
+fn assert_jacobi(chevalley: &ChevalleyBasis) {
+    let mut f: HashMap<(usize, usize), Vec<(usize, i32)>> = HashMap::new();
+    for (a, b, c, x) in chevalley.structure_constants.iter() {
+        f.entry((*a, *b)).or_default().push((*c, *x));
+    }
+    let bracket = |x: &HashMap<usize, i32>, c: usize| {
+        let mut res: HashMap<usize, i32> = HashMap::new();
+        for (a, x_a) in x {
+            for (d, f_acd) in f.get(&(*a, c)).into_iter().flatten() {
+                *res.entry(*d).or_default() += x_a * f_acd;
+            }
+        }
+        res
+    };
+    let single = |a: usize, b: usize| {
+        let mut x = HashMap::new();
+        for (c, f_abc) in f.get(&(a, b)).into_iter().flatten() {
+            x.insert(*c, *f_abc);
+        }
+        x
+    };
+
+    let m = chevalley.dim();
+    for a in 0..m {
+        for b in a + 1..m {
+            for c in b + 1..m {
+                let mut sum: HashMap<usize, i32> = HashMap::new();
+                for (x, y, z) in [(a, b, c), (b, c, a), (c, a, b)].iter() {
+                    for (d, v) in bracket(&single(*x, *y), *z) {
+                        *sum.entry(d).or_default() += v;
+                    }
+                }
+                assert!(
+                    sum.values().all(|v| *v == 0),
+                    "{}: Jacobi fails for {} {} {}",
+                    chevalley.cartan_type,
+                    a,
+                    b,
+                    c
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_cartan_matrices() {
+    for t in all_types() {
+        let classification = classify_cartan_matrix(&t.cartan_matrix());
+        assert_eq!(classification.components.len(), 1);
+        assert_eq!(classification.components[0].cartan_type, t);
+        // Bourbaki numbering
+        let nodes = &classification.components[0].nodes;
+        if t.series() != 'D' && t.series() != 'E' && t.series() != 'G' {
+            assert_eq!(nodes, &(0..t.rank()).collect::<Vec<_>>());
+        }
+    }
+}
+
+#[test]
+fn test_dimensions() {
+    let dim = |s: &str| ChevalleyBasis::new(s.parse().unwrap()).dim();
+    assert_eq!(dim("A1"), 3);
+    assert_eq!(dim("A4"), 24);
+    assert_eq!(dim("B3"), 21);
+    assert_eq!(dim("C4"), 36);
+    assert_eq!(dim("D5"), 45);
+    assert_eq!(dim("G2"), 14);
+    assert_eq!(dim("F4"), 52);
+    assert_eq!(dim("E6"), 78);
+    assert_eq!(dim("E7"), 133);
+    assert_eq!(dim("E8"), 248);
+
+    // The highest root of E8
+    let e8 = ChevalleyBasis::new(CartanType::new('E', 8).unwrap());
+    assert_eq!(
+        e8.positive_roots.last().unwrap(),
+        &vec![2, 3, 4, 6, 5, 4, 3, 2]
+    );
+}
+
+#[test]
+fn test_chevalley_relations() {
+    for t in all_types() {
+        let chevalley = ChevalleyBasis::new(t);
+        let r = t.rank();
+
+        for x in chevalley.positive_roots.iter() {
+            let minus_x: Vec<i32> = x.iter().map(|a| -a).collect();
+            for y in chevalley.positive_roots.iter() {
+                for (x, y) in [(x.clone(), y.clone()), (minus_x.clone(), y.clone())].iter() {
+                    let sum: Vec<i32> = x.iter().zip(y).map(|(a, b)| a + b).collect();
+                    let n = chevalley.n(x, y);
+                    if chevalley.root_index(&sum).is_none() {
+                        assert_eq!(n, 0);
+                        continue;
+                    }
+                    // N = +-(p + 1)
+                    let mut p = 0;
+                    loop {
+                        let z: Vec<i32> = y.iter().zip(x).map(|(b, a)| b - (p + 1) * a).collect();
+                        if chevalley.root_index(&z).is_none() {
+                            break;
+                        }
+                        p += 1;
+                    }
+                    assert_eq!(n.abs(), p + 1, "{}", t);
+                }
+            }
+
+            // [e_x, e_-x] = h_x has integer coefficients
+            let a = chevalley.root_index(x).unwrap();
+            let b = chevalley.root_index(&minus_x).unwrap();
+            let h: Vec<_> = chevalley
+                .structure_constants
+                .iter()
+                .filter(|(i, j, _, _)| *i == a && *j == b)
+                .collect();
+            assert!(!h.is_empty());
+            assert!(h.iter().all(|(_, _, c, _)| chevalley.root(*c).is_none()));
+        }
+
+        // [h_i, e_j] = A_ji e_j
+        for i in 0..r {
+            for j in 0..r {
+                let mut alpha = vec![0; r];
+                alpha[j] = 1;
+                let e = chevalley.root_index(&alpha).unwrap();
+                let x = chevalley
+                    .structure_constants
+                    .iter()
+                    .find(|(a, b, _, _)| *a == chevalley.cartan_index(i) && *b == e)
+                    .map_or(0, |x| x.3);
+                assert_eq!(x, chevalley.cartan_matrix[[j, i]]);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_jacobi() {
+    for t in ["A3", "B3", "C3", "D4", "G2", "F4", "E6"].iter() {
+        assert_jacobi(&ChevalleyBasis::new(t.parse().unwrap()));
+    }
+}
+
+#[test]
+fn test_defining_representation() {
+    for (t, n) in [
+        ("A3", 4),
+        ("B2", 5),
+        ("B3", 7),
+        ("C3", 6),
+        ("D4", 8),
+        ("D5", 10),
+    ]
+    .iter()
+    {
+        let chevalley = ChevalleyBasis::new(t.parse().unwrap());
+        let rep = chevalley.defining_representation().unwrap();
+        assert_eq!(rep.len(), chevalley.dim());
+        assert_eq!(rep[0].shape(), &[*n, *n]);
+
+        // The matrices are independent and have the exact structure constants
+        let report = verify_lie_algebra(&rep);
+        assert!(report.is_independent());
+        let f = chevalley.to_structure_constants();
+        for (a, x) in rep.iter().enumerate() {
+            for (b, y) in rep.iter().enumerate() {
+                let mut expected = ndarray::Array2::zeros((*n, *n));
+                for (c, f_abc) in f.pair(a, b) {
+                    expected.scaled_add(*f_abc, &rep[*c]);
+                }
+                assert_abs_diff_eq!(x.dot(y) - y.dot(x), expected, epsilon = 1e-12);
+            }
+        }
+    }
+
+    assert!(ChevalleyBasis::new(CartanType::new('G', 2).unwrap())
+        .defining_representation()
+        .is_none());
+}
+
+#[test]
+fn test_from_cartan_type() {
+    let g2 = LieAlgebra::from_cartan_type("G2").unwrap();
+    assert_eq!(g2.dim(), 14);
+    assert_eq!(g2.matrix_dim(), 14);
+    assert!(verify_lie_algebra(g2.basis()).is_closed());
+    assert!(verify_lie_algebra(g2.basis()).satisfies_jacobi());
+    assert_eq!(classify(&g2).cartan_type(), "G2");
+
+    let b3 = LieAlgebra::from_cartan_type("B3").unwrap();
+    assert_eq!(b3.matrix_dim(), 7);
+    assert_eq!(classify(&b3).cartan_type(), "B3");
+
+    // The structure constants are exact, and agree with the basis
+    assert_abs_diff_eq!(
+        find_structure_constants(b3.basis()).to_dense(),
+        b3.structure_constants().to_dense(),
+        epsilon = 1e-10
+    );
+
+    for t in ["F4", "E6"].iter() {
+        assert_eq!(
+            classify(&LieAlgebra::from_cartan_type(t).unwrap()).cartan_type(),
+            *t
+        );
+    }
+}
+
+#[test]
+fn test_e8() {
+    let e8 = ChevalleyBasis::new("E8".parse().unwrap());
+    // N = +-1 for a simply laced algebra
+    assert!(e8
+        .structure_constants
+        .iter()
+        .filter(|(a, b, c, _)| e8.root(*a).is_some()
+            && e8.root(*b).is_some()
+            && e8.root(*c).is_some())
+        .all(|x| x.3.abs() == 1));
+    assert_eq!(LieAlgebra::from_cartan_type("E8").unwrap().dim(), 248);
+}
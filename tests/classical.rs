@@ -0,0 +1,140 @@
+use approx::assert_abs_diff_eq;
+use lie::classical::*;
+use lie::classify::*;
+use lie::killing::*;
+use lie::lie_algebra::*;
+use lie::verify::*;
+use ndarray as nd;
+use num_complex::Complex64;
+
+fn trace_overlap(x: &nd::Array2<Complex64>, y: &nd::Array2<Complex64>) -> Complex64 {
+    (x.mapv(|x| x.conj()) * y).sum()
+}
+
+#[test]
+fn test_dimensions_and_closure() {
+    let n = 4;
+    let cases: Vec<(Vec<nd::Array2<Complex64>>, usize, usize)> = vec![
+        (gen_gl(n, Normalization::Unit), n * n, n),
+        (gen_sl(n, Normalization::Unit), n * n - 1, n),
+        (gen_so(n, Normalization::Unit), n * (n - 1) / 2, n),
+        (gen_sp(n, Normalization::Unit), n * (2 * n + 1), 2 * n),
+        (gen_u(n, Normalization::Unit), n * n, n),
+    ];
+
+    for (basis, dim, size) in cases.iter() {
+        assert_eq!(basis.len(), *dim);
+        assert_eq!(basis[0].shape(), &[*size, *size]);
+
+        let report = verify_lie_algebra(basis);
+        assert!(report.is_lie_algebra());
+
+        // Every basis is orthogonal
+        for (a, x) in basis.iter().enumerate() {
+            for y in basis.iter().skip(a + 1) {
+                assert_abs_diff_eq!(trace_overlap(x, y).norm(), 0., epsilon = 1e-12);
+            }
+        }
+    }
+
+    assert!(verify_lie_algebra(&gen_so(n, Normalization::Unit)).is_traceless());
+    assert!(verify_lie_algebra(&gen_sp(n, Normalization::Unit)).is_traceless());
+    assert!(verify_lie_algebra(&gen_u(n, Normalization::Unit)).is_anti_hermitian());
+}
+
+#[test]
+fn test_normalization() {
+    for c in [0.5, 1., 2.].iter() {
+        let normalization = Normalization::Trace(*c);
+        for basis in [
+            gen_gl(3, normalization),
+            gen_sl(3, normalization),
+            gen_so(5, normalization),
+            gen_sp(2, normalization),
+            gen_u(3, normalization),
+        ]
+        .iter()
+        {
+            for x in basis {
+                assert_abs_diff_eq!(trace_overlap(x, x).re, *c, epsilon = 1e-12);
+            }
+        }
+    }
+
+    // The su(n) part of u(n) is gen_gellmann
+    let u = gen_u(3, Normalization::Trace(2.));
+    let su = lie::gellmann::gen_gellmann(3);
+    for (x, y) in u.iter().zip(su.iter()) {
+        assert_abs_diff_eq!(x, y, epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn test_structure_constants() {
+    // [E_01 - E_10, E_12 - E_21] = E_02 - E_20 in so(3)
+    let so3 = gen_so(3, Normalization::Unit);
+    let f = find_structure_constants(&so3);
+    assert_abs_diff_eq!(f.get(0, 2, 1).re, 1., epsilon = 1e-10);
+
+    // Rescaling every element by s rescales the structure constants by s
+    let f_normalized = find_structure_constants(&gen_so(3, Normalization::Trace(8.)));
+    assert_abs_diff_eq!(f_normalized.get(0, 2, 1).re, 2., epsilon = 1e-10);
+
+    // [E_01, E_12] = E_02 in gl(3)
+    let gl = gen_gl(3, Normalization::Unit);
+    let f = find_structure_constants(&gl);
+    assert_abs_diff_eq!(f.get(1, 5, 2).re, 1., epsilon = 1e-10);
+}
+
+#[test]
+fn test_types() {
+    let cartan_type =
+        |basis: Vec<nd::Array2<Complex64>>| classify(&LieAlgebra::new(basis)).cartan_type();
+    assert_eq!(cartan_type(gen_sl(4, Normalization::Unit)), "A3");
+    assert_eq!(cartan_type(gen_so(7, Normalization::Unit)), "B3");
+    assert_eq!(cartan_type(gen_so(8, Normalization::Unit)), "D4");
+    assert_eq!(cartan_type(gen_sp(3, Normalization::Unit)), "C3");
+
+    // u(n) has a center, and so(n) is compact
+    let f = find_structure_constants(&gen_u(3, Normalization::Unit));
+    assert!(!is_semisimple(&f));
+    let f = find_structure_constants(&gen_so(4, Normalization::Unit));
+    assert!(is_compact(&f));
+}
+
+#[test]
+fn test_small_n() {
+    for &n in [0, 1].iter() {
+        assert!(gen_sl(n, Normalization::Unit).is_empty());
+        assert!(gen_so(n, Normalization::Unit).is_empty());
+        assert_eq!(gen_gl(n, Normalization::Unit).len(), n * n);
+        assert_eq!(gen_u(n, Normalization::Unit).len(), n * n);
+        assert_eq!(gen_sp(n, Normalization::Unit).len(), n * (2 * n + 1));
+    }
+}
+
+#[test]
+fn test_d_tensor_with_identity() {
+    // The identity is in the span of u(n) and gl(n), so it takes no separate coefficient
+    for basis in [
+        gen_u(3, Normalization::Unit),
+        gen_gl(2, Normalization::Unit),
+    ]
+    .iter()
+    {
+        let algebra = LieAlgebra::new(basis.to_vec());
+        let d = algebra.d_tensor();
+        assert!(d.identity.iter().all(|x| x.norm() == 0.));
+
+        for a in 0..basis.len() {
+            for b in 0..basis.len() {
+                let mut expected = nd::Array2::zeros(basis[0].dim());
+                for (c, t_c) in basis.iter().enumerate() {
+                    expected.scaled_add(d.d.get(a, b, c), t_c);
+                }
+                let anticommutator = basis[a].dot(&basis[b]) + basis[b].dot(&basis[a]);
+                assert_abs_diff_eq!(anticommutator, expected, epsilon = 1e-10);
+            }
+        }
+    }
+}
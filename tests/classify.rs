@@ -0,0 +1,170 @@
+use lie::classify::*;
+use lie::gellmann::*;
+use lie::lie_algebra::*;
+use lie::spherical::*;
+use ndarray as nd;
+
+fn classify_matrix(a: &[&[i32]]) -> Classification {
+    let r = a.len();
+    let a = nd::Array2::from_shape_fn((r, r), |(i, j)| a[i][j]);
+    classify_cartan_matrix(&a)
+}
+
+#[test]
+fn test_classify_su_n() {
+    let classification = classify(&LieAlgebra::new(gen_gellmann(3)));
+    assert_eq!(classification.cartan_type(), "A2");
+    assert_eq!(classification.dynkin_diagram(), "1---2");
+
+    assert_eq!(
+        classify(&LieAlgebra::new(gen_gellmann(4))).cartan_type(),
+        "A3"
+    );
+    assert_eq!(
+        classify(&LieAlgebra::new(gen_gellmann(5))).dynkin_diagram(),
+        "1---2---3---4"
+    );
+
+    // The spin 1/2 spherical tensor basis spans su(2)
+    let basis = hermitian_basis_from_spin(0.5);
+    assert_eq!(classify(&LieAlgebra::new(basis)).cartan_type(), "A1");
+}
+
+#[test]
+fn test_classify_dynkin_diagrams() {
+    use lie::classical::*;
+
+    // The labels are the positions of the simple roots found in each algebra, and the arrow
+    // points from the long roots to the short ones
+    let dynkin = |algebra: LieAlgebra| {
+        let classification = classify(&algebra);
+        (
+            classification.cartan_type(),
+            classification.dynkin_diagram(),
+        )
+    };
+    assert_eq!(
+        dynkin(LieAlgebra::new(gen_so(7, Normalization::Unit))),
+        ("B3".to_string(), "1---2=>=3".to_string())
+    );
+    assert_eq!(
+        dynkin(LieAlgebra::new(gen_sp(3, Normalization::Unit))),
+        ("C3".to_string(), "2---1=<=3".to_string())
+    );
+    assert_eq!(
+        dynkin(LieAlgebra::new(gen_so(8, Normalization::Unit))),
+        ("D4".to_string(), "1---4---2\n    |\n    3".to_string())
+    );
+    assert_eq!(
+        dynkin(LieAlgebra::new(gen_so(10, Normalization::Unit))),
+        (
+            "D5".to_string(),
+            "5---4---3---1\n        |\n        2".to_string()
+        )
+    );
+    assert_eq!(
+        dynkin(LieAlgebra::from_cartan_type("G2").unwrap()),
+        ("G2".to_string(), "2≡>≡1".to_string())
+    );
+}
+
+#[test]
+fn test_classify_structure_constants() {
+    let f = find_structure_constants(&gen_gellmann(3));
+    assert_eq!(classify_structure_constants(&f).cartan_type(), "A2");
+
+    // The closed form structure constants need no matrices at all
+    let f = structure_constants(4);
+    assert_eq!(classify_structure_constants(&f).cartan_type(), "A3");
+}
+
+#[test]
+fn test_classify_direct_sum() {
+    // su(3) + su(2) as block diagonal 5 x 5 matrices
+    let blocks = |x: &nd::Array2<_>, offset: usize| {
+        let mut m = nd::Array2::zeros((5, 5));
+        let n = x.shape()[0];
+        m.slice_mut(nd::s![offset..offset + n, offset..offset + n])
+            .assign(x);
+        m
+    };
+    let mut basis: Vec<_> = gen_gellmann(3).iter().map(|x| blocks(x, 0)).collect();
+    basis.extend(gen_gellmann(2).iter().map(|x| blocks(x, 3)));
+
+    let classification = classify(&LieAlgebra::new(basis));
+    assert_eq!(classification.cartan_type(), "A2 + A1");
+    assert_eq!(classification.components.len(), 2);
+}
+
+#[test]
+fn test_classify_cartan_matrices() {
+    let b3 = classify_matrix(&[&[2, -1, 0], &[-1, 2, -2], &[0, -1, 2]]);
+    assert_eq!(b3.cartan_type(), "B3");
+    assert_eq!(b3.dynkin_diagram(), "1---2=>=3");
+
+    let c3 = classify_matrix(&[&[2, -1, 0], &[-1, 2, -1], &[0, -2, 2]]);
+    assert_eq!(c3.cartan_type(), "C3");
+    assert_eq!(c3.dynkin_diagram(), "1---2=<=3");
+
+    let g2 = classify_matrix(&[&[2, -1], &[-3, 2]]);
+    assert_eq!(g2.cartan_type(), "G2");
+    assert_eq!(g2.dynkin_diagram(), "2≡>≡1");
+
+    let f4 = classify_matrix(&[
+        &[2, -1, 0, 0],
+        &[-1, 2, -2, 0],
+        &[0, -1, 2, -1],
+        &[0, 0, -1, 2],
+    ]);
+    assert_eq!(f4.cartan_type(), "F4");
+    assert_eq!(f4.dynkin_diagram(), "1---2=>=3---4");
+
+    let d4 = classify_matrix(&[
+        &[2, -1, 0, 0],
+        &[-1, 2, -1, -1],
+        &[0, -1, 2, 0],
+        &[0, -1, 0, 2],
+    ]);
+    assert_eq!(d4.cartan_type(), "D4");
+
+    // Bourbaki labelling of E6, with node 2 attached to node 4
+    let e6 = classify_matrix(&[
+        &[2, 0, -1, 0, 0, 0],
+        &[0, 2, 0, -1, 0, 0],
+        &[-1, 0, 2, -1, 0, 0],
+        &[0, -1, -1, 2, -1, 0],
+        &[0, 0, 0, -1, 2, -1],
+        &[0, 0, 0, 0, -1, 2],
+    ]);
+    assert_eq!(e6.cartan_type(), "E6");
+    assert_eq!(
+        e6.dynkin_diagram(),
+        "1---3---4---5---6\n        |\n        2"
+    );
+
+    // so(4) = su(2) + su(2), and B2 with an A1
+    let sum = classify_matrix(&[&[2, -2, 0], &[-1, 2, 0], &[0, 0, 2]]);
+    assert_eq!(sum.cartan_type(), "B2 + A1");
+}
+
+#[test]
+fn test_cartan_type_strings() {
+    let e8: CartanType = "E8".parse().unwrap();
+    assert_eq!(e8, CartanType::new('E', 8).unwrap());
+    assert_eq!((e8.series(), e8.rank()), ('E', 8));
+    assert_eq!(e8.to_string(), "E8");
+
+    assert!("E9".parse::<CartanType>().is_err());
+    assert!("X2".parse::<CartanType>().is_err());
+
+    // Low rank coincidences map to a single type, and the rest are not simple
+    let parse = |s: &str| s.parse::<CartanType>().map(|x| x.to_string());
+    assert_eq!(parse("B1"), Ok("A1".to_string()));
+    assert_eq!(parse("C2"), Ok("B2".to_string()));
+    assert_eq!(parse("D3"), Ok("A3".to_string()));
+    assert_eq!(CartanType::new('C', 2), CartanType::new('B', 2));
+    assert!(parse("D2").unwrap_err().contains("not simple"));
+    assert!(parse("A0").is_err());
+    assert!(CartanType::new('E', 3).unwrap_err().contains("exceptional"));
+    assert!(CartanType::new('X', 2).is_err());
+}
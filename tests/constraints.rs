@@ -0,0 +1,86 @@
+use approx::assert_abs_diff_eq;
+use lie::classical::*;
+use lie::constraints::*;
+use lie::killing::*;
+use lie::lie_algebra::*;
+use lie::real_forms::*;
+use lie::verify::*;
+use ndarray as nd;
+use num_complex::Complex64;
+
+#[test]
+fn test_orthogonal() {
+    let identity = nd::Array2::eye(4);
+    let so = algebra_from_constraints(
+        4,
+        &[Constraint::Bilinear(identity.clone()), Constraint::Real],
+    );
+
+    // The standard basis is the one of gen_so
+    let expected = gen_so(4, Normalization::Unit);
+    assert_eq!(so.len(), expected.len());
+    for (x, y) in so.iter().zip(expected.iter()) {
+        assert_abs_diff_eq!(x, y, epsilon = 1e-10);
+    }
+
+    // Without the reality condition, so(4, C) as a real algebra
+    let so_c = algebra_from_constraints(4, &[Constraint::Bilinear(identity)]);
+    assert_eq!(so_c.len(), 12);
+}
+
+#[test]
+fn test_symplectic_and_unitary() {
+    let n = 2;
+    let sp = algebra_from_constraints(
+        2 * n,
+        &[Constraint::Bilinear(symplectic_form(n)), Constraint::Real],
+    );
+    assert_eq!(sp.len(), n * (2 * n + 1));
+    assert!(verify_lie_algebra(&sp).is_lie_algebra());
+
+    let su = algebra_from_constraints(
+        3,
+        &[
+            Constraint::Sesquilinear(nd::Array2::eye(3)),
+            Constraint::Traceless,
+        ],
+    );
+    assert_eq!(su.len(), 8);
+    let report = verify_lie_algebra(&su);
+    assert!(report.is_lie_algebra());
+    assert!(report.is_anti_hermitian());
+    assert!(report.is_traceless());
+    assert!(is_compact(&find_structure_constants(&su)));
+
+    let su11 = algebra_from_constraints(
+        2,
+        &[
+            Constraint::Sesquilinear(signature_metric(1, 1)),
+            Constraint::Traceless,
+        ],
+    );
+    assert_eq!(su11.len(), 3);
+    let f = find_structure_constants(&su11);
+    assert_eq!(signature(&killing_form(&f)), (2, 1, 0));
+}
+
+#[test]
+fn test_commutant() {
+    // The elements of u(3) that commute with diag(1, 1, 2) form u(2) + u(1)
+    let a = nd::Array2::from_diag(&nd::arr1(&[1., 1., 2.]).mapv(|x| Complex64::new(x, 0.)));
+    let basis = algebra_from_constraints(
+        3,
+        &[
+            Constraint::Sesquilinear(nd::Array2::eye(3)),
+            Constraint::Commutes(a.clone()),
+        ],
+    );
+    assert_eq!(basis.len(), 5);
+    for x in basis.iter() {
+        assert_abs_diff_eq!(x.dot(&a), a.dot(x), epsilon = 1e-10);
+    }
+    assert!(verify_lie_algebra(&basis).is_lie_algebra());
+
+    // Without constraints, gl(n) over the reals
+    assert_eq!(algebra_from_constraints(2, &[]).len(), 8);
+}
@@ -0,0 +1,193 @@
+use approx::assert_abs_diff_eq;
+use lie::euler::*;
+use lie::exponential::*;
+use lie::gellmann::*;
+use lie::lie_algebra::*;
+use lie::random::*;
+use lie::su2::*;
+use ndarray as nd;
+use ndarray_linalg::*;
+use num_complex::Complex64;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::f64::consts::PI;
+
+fn scaled(x: &nd::Array2<Complex64>, c: Complex64) -> nd::Array2<Complex64> {
+    x * c
+}
+
+#[test]
+fn test_su2_euler() {
+    let [s_z, s_x, i_s_y] = gen_su2(0.5);
+    let (alpha, beta, gamma) = (0.7, 1.9, -2.4);
+    let rz = |theta: f64| expm(&scaled(&s_z, Complex64::new(0., -theta)));
+
+    // exp(-i beta S_y) = exp(-beta (i S_y))
+    let zyz = rz(alpha)
+        .dot(&expm(&scaled(&i_s_y, Complex64::new(-beta, 0.))))
+        .dot(&rz(gamma));
+    assert_abs_diff_eq!(
+        su2_from_euler(alpha, beta, gamma, EulerConvention::Zyz),
+        zyz,
+        epsilon = 1e-12
+    );
+    let zxz = rz(alpha)
+        .dot(&expm(&scaled(&s_x, Complex64::new(0., -beta))))
+        .dot(&rz(gamma));
+    assert_abs_diff_eq!(
+        su2_from_euler(alpha, beta, gamma, EulerConvention::Zxz),
+        zxz,
+        epsilon = 1e-12
+    );
+
+    let mut rng = StdRng::seed_from_u64(23);
+    for convention in [EulerConvention::Zyz, EulerConvention::Zxz].iter() {
+        for _ in 0..50 {
+            let u = haar_special_unitary(2, &mut rng);
+            let angles = su2_to_euler(&u, *convention);
+            assert!(!angles.gimbal_lock);
+            assert!(angles.alpha > -PI && angles.alpha <= PI);
+            assert!(angles.beta >= 0. && angles.beta <= PI);
+            assert!(angles.gamma > -2. * PI && angles.gamma <= 2. * PI);
+            let v = su2_from_euler(angles.alpha, angles.beta, angles.gamma, *convention);
+            assert_abs_diff_eq!(u, v, epsilon = 1e-10);
+        }
+
+        // The angles are recovered when they are in range
+        let angles = su2_to_euler(
+            &su2_from_euler(alpha, beta, gamma, *convention),
+            *convention,
+        );
+        assert_abs_diff_eq!(angles.alpha, alpha, epsilon = 1e-10);
+        assert_abs_diff_eq!(angles.beta, beta, epsilon = 1e-10);
+        assert_abs_diff_eq!(angles.gamma, gamma, epsilon = 1e-10);
+
+        // In gimbal lock, only alpha + gamma or alpha - gamma is kept
+        for beta in [0., PI].iter() {
+            let u = su2_from_euler(1.1, *beta, 0.4, *convention);
+            let angles = su2_to_euler(&u, *convention);
+            assert!(angles.gimbal_lock);
+            assert_eq!(angles.alpha, 0.);
+            let v = su2_from_euler(angles.alpha, angles.beta, angles.gamma, *convention);
+            assert_abs_diff_eq!(u, v, epsilon = 1e-10);
+        }
+    }
+
+    // The measure integrates to 1
+    let steps = 2000;
+    let integral: f64 = (0..steps)
+        .map(|k| su2_euler_measure(PI * (k as f64 + 0.5) / steps as f64) * PI / steps as f64)
+        .sum::<f64>()
+        * 2.
+        * PI
+        * 4.
+        * PI;
+    assert_abs_diff_eq!(integral, 1., epsilon = 1e-6);
+}
+
+fn random_angles<R: Rng>(n: usize, rng: &mut R) -> Vec<f64> {
+    su_euler_ranges(n)
+        .iter()
+        .map(|x| x * rng.gen::<f64>())
+        .collect()
+}
+
+#[test]
+fn test_su_n_euler() {
+    let mut rng = StdRng::seed_from_u64(29);
+    for n in 2..6 {
+        let generators = su_euler_generators(n);
+        assert_eq!(generators.len(), n * n - 1);
+        assert_eq!(su_euler_ranges(n).len(), n * n - 1);
+
+        // The product of exponentials of the Gell-Mann generators
+        let basis = gen_gellmann(n);
+        let angles = random_angles(n, &mut rng);
+        let mut expected: nd::Array2<Complex64> = nd::Array2::eye(n);
+        for (g, theta) in generators.iter().zip(angles.iter()) {
+            expected = expected.dot(&expm(&scaled(&basis[*g], Complex64::new(*theta, 0.))));
+        }
+        let u = su_from_euler(n, &angles);
+        assert_abs_diff_eq!(u, expected, epsilon = 1e-10);
+
+        // The angles are recovered from the matrix
+        assert_abs_diff_eq!(
+            nd::arr1(&su_to_euler(&u)),
+            nd::arr1(&angles),
+            epsilon = 1e-8
+        );
+
+        for _ in 0..20 {
+            let u = haar_special_unitary(n, &mut rng);
+            let angles = su_to_euler(&u);
+            for (x, range) in angles.iter().zip(su_euler_ranges(n).iter()) {
+                assert!(*x >= 0. && *x < range + 1e-12);
+            }
+            assert_abs_diff_eq!(su_from_euler(n, &angles), u, epsilon = 1e-9);
+        }
+    }
+
+    // The SU(3) sequence of Tilma and Sudarshan: l3, l2, l3, l5, l3, l2, l3, l8
+    assert_eq!(su_euler_generators(3), vec![0, 1, 0, 2, 0, 1, 0, 4]);
+    // Degenerate columns
+    let u = su_from_euler(3, &[0., 0., 0., PI / 2., 0., 0., 0., 0.]);
+    assert_abs_diff_eq!(su_from_euler(3, &su_to_euler(&u)), u, epsilon = 1e-10);
+    assert_abs_diff_eq!(
+        su_from_euler(3, &su_to_euler(&nd::Array2::eye(3))),
+        nd::Array2::eye(3),
+        epsilon = 1e-10
+    );
+}
+
+#[test]
+fn test_su_n_measure() {
+    // The measure is the volume of the left-invariant forms U^-1 dU
+    let mut rng = StdRng::seed_from_u64(31);
+    for n in 2..5 {
+        let algebra = LieAlgebra::new(gen_gellmann(n));
+        let dim = n * n - 1;
+        let jacobian = |angles: &[f64]| {
+            let u_inv = su_from_euler(n, angles).t().mapv(|x| x.conj());
+            let h = 1e-6;
+            let m = nd::Array2::from_shape_fn((dim, dim), |(k, a)| {
+                let mut plus = angles.to_vec();
+                let mut minus = angles.to_vec();
+                plus[k] += h;
+                minus[k] -= h;
+                let du = (su_from_euler(n, &plus) - su_from_euler(n, &minus)) / (2. * h);
+                algebra.to_coordinates(&u_inv.dot(&du))[a].re
+            });
+            m.det().unwrap().abs()
+        };
+
+        let ratios: Vec<f64> = (0..5)
+            .map(|_| {
+                let angles = random_angles(n, &mut rng);
+                su_euler_measure(n, &angles) / jacobian(&angles)
+            })
+            .collect();
+        // The ranges cover SU(n) once: Vol(SU(n)) = sqrt(n) (2 pi)^((n^2 + n - 2) / 2) /
+        // prod_k k! for the metric Tr(X^dagger Y), with Tr(T_a^dagger T_a) = 2 here
+        let nf = n as f64;
+        let factorials: f64 = (1..n).map(|k| (1..=k).product::<usize>() as f64).product();
+        let volume = nf.sqrt() * (2. * PI).powf((nf * nf + nf - 2.) / 2.)
+            / factorials
+            / 2f64.powf((nf * nf - 1.) / 2.);
+        for r in ratios.iter() {
+            assert_abs_diff_eq!(r * volume, 1., epsilon = 1e-5);
+        }
+    }
+
+    // E|U_nn|^2 = 1/n for the Haar measure, with |U_nn| = cos b_n
+    let n = 3;
+    let mut rng = StdRng::seed_from_u64(37);
+    let samples = 20000;
+    let mean = (0..samples)
+        .map(|_| {
+            let angles = su_to_euler(&haar_special_unitary(n, &mut rng));
+            angles[2 * (n - 1) - 1].cos().powi(2)
+        })
+        .sum::<f64>()
+        / samples as f64;
+    assert_abs_diff_eq!(mean, 1. / n as f64, epsilon = 0.01);
+}
@@ -0,0 +1,278 @@
+use approx::assert_abs_diff_eq;
+use lie::classical::*;
+use lie::exponential::*;
+use lie::gellmann::*;
+use lie::lie_algebra::*;
+use lie::su2::*;
+use lie::sylvester::*;
+use ndarray as nd;
+use ndarray_linalg::*;
+use num_complex::Complex64;
+
+fn coords(dim: usize, scale: f64) -> nd::Array1<Complex64> {
+    nd::Array1::from_shape_fn(dim, |a| {
+        Complex64::new(scale * (0.37 * a as f64 + 0.21).sin(), 0.)
+    })
+}
+
+fn assert_unitary(u: &nd::Array2<Complex64>) {
+    let n = u.shape()[0];
+    let identity: nd::Array2<Complex64> = nd::Array2::eye(n);
+    assert_abs_diff_eq!(u.t().mapv(|x| x.conj()).dot(u), identity, epsilon = 1e-10);
+}
+
+#[test]
+fn test_su_n() {
+    for n in 3..6 {
+        let algebra = LieAlgebra::new(gen_gellmann(n));
+        let c = coords(algebra.dim(), 1.7);
+        let u = exp_map(&algebra, &c);
+
+        assert_unitary(&u);
+        assert_abs_diff_eq!(u.det().unwrap(), Complex64::new(1., 0.), epsilon = 1e-10);
+        assert_abs_diff_eq!(u, expm(&algebra.from_coordinates(&c)), epsilon = 1e-10);
+    }
+}
+
+#[test]
+fn test_su2() {
+    let algebra = LieAlgebra::new(gen_gellmann(2));
+
+    // A rotation by 2 pi is -1 on spin 1/2: T = i sigma, so exp(i pi sigma_z) = -1
+    let mut c = nd::Array1::zeros(3);
+    c[2] = Complex64::new(std::f64::consts::PI, 0.);
+    assert_abs_diff_eq!(
+        exp_map(&algebra, &c),
+        -nd::Array2::<Complex64>::eye(2),
+        epsilon = 1e-12
+    );
+
+    for scale in [1e-9, 0.3, 2., 40.].iter() {
+        let c = coords(3, *scale);
+        let u = exp_map(&algebra, &c);
+        assert_unitary(&u);
+        assert_abs_diff_eq!(u, expm(&algebra.from_coordinates(&c)), epsilon = 1e-9);
+    }
+
+    // Complex coordinates give sl(2, C)
+    let c = coords(3, 0.8).mapv(|x| x * Complex64::new(1., 0.5));
+    assert_abs_diff_eq!(
+        exp_map(&algebra, &c),
+        expm(&algebra.from_coordinates(&c)),
+        epsilon = 1e-10
+    );
+}
+
+#[test]
+fn test_spin_j() {
+    // The closed form is used up to j = 4, and larger spins fall back to the eigendecomposition
+    // for imaginary coordinates, or to expm for real and complex ones
+    for j in [1., 1.5, 2., 4., 4.5, 7.].iter() {
+        let algebra = LieAlgebra::new(gen_su2(*j).to_vec());
+        let n = algebra.matrix_dim();
+
+        // exp(-2 pi i S_z) is (-1)^(2j)
+        let c = nd::arr1(&[
+            Complex64::new(0., -2. * std::f64::consts::PI),
+            Complex64::new(0., 0.),
+            Complex64::new(0., 0.),
+        ]);
+        let sign = if (2. * j) as usize % 2 == 0 { 1. } else { -1. };
+        assert_abs_diff_eq!(
+            exp_map(&algebra, &c),
+            nd::Array2::<Complex64>::eye(n) * sign,
+            epsilon = 1e-10
+        );
+
+        // A rotation exp(-i theta n.S): S_z, S_x and i S_y with coordinates -i a_z, -i a_x, -a_y
+        let a = [0.4, -1.1, 0.7];
+        let c = nd::arr1(&[
+            Complex64::new(0., -a[2]),
+            Complex64::new(0., -a[0]),
+            Complex64::new(-a[1], 0.),
+        ]);
+        let u = exp_map(&algebra, &c);
+        assert_unitary(&u);
+        assert_abs_diff_eq!(u, expm(&algebra.from_coordinates(&c)), epsilon = 1e-9);
+
+        // Real coordinates give the noncompact real form spanned by S_z, S_x and i S_y
+        let c = coords(3, 0.9);
+        assert_abs_diff_eq!(
+            exp_map(&algebra, &c),
+            expm(&algebra.from_coordinates(&c)),
+            epsilon = 1e-9
+        );
+    }
+}
+
+#[test]
+fn test_batch() {
+    let algebra = LieAlgebra::new(gen_gellmann(3));
+    let batch = nd::Array2::from_shape_fn((20, 8), |(k, a)| {
+        Complex64::new((0.3 * k as f64 + 0.7 * a as f64).cos(), 0.)
+    });
+    let us = exp_map_batch(&algebra, &batch);
+    assert_eq!(us.len(), 20);
+    for (u, c) in us.iter().zip(batch.genrows()) {
+        assert_abs_diff_eq!(*u, exp_map(&algebra, &c.to_owned()), epsilon = 1e-14);
+    }
+}
+
+// exp(x) by its Taylor series, independently of the crate
+fn taylor_exp(x: &nd::Array2<Complex64>) -> nd::Array2<Complex64> {
+    let mut term = nd::Array2::eye(x.shape()[0]);
+    let mut res = term.clone();
+    for k in 1..80 {
+        term = term.dot(x) / Complex64::new(k as f64, 0.);
+        res += &term;
+    }
+    res
+}
+
+#[test]
+fn test_spin_j_near_nilpotent() {
+    // S_x + i S_y is nilpotent, so kappa is tiny next to the size of the element
+    for &j in [2., 4.].iter() {
+        let algebra = LieAlgebra::new(gen_su2(j).to_vec());
+        for &delta in [1e-6, 1e-9].iter() {
+            let c = nd::arr1(&[
+                Complex64::new(0., 0.),
+                Complex64::new(1., 0.),
+                Complex64::new(1. + delta, 0.),
+            ]);
+            assert_abs_diff_eq!(
+                exp_map(&algebra, &c),
+                taylor_exp(&algebra.from_coordinates(&c)),
+                epsilon = 1e-9
+            );
+        }
+    }
+}
+
+#[test]
+fn test_log_map() {
+    for n in 2..5 {
+        let su = LieAlgebra::new(gen_gellmann(n));
+        for basis in [gen_gellmann(n), gen_sylvester(n)].iter() {
+            let algebra = LieAlgebra::new(basis.to_vec());
+            let element =
+                |scale| algebra.to_coordinates(&su.from_coordinates(&coords(su.dim(), scale)));
+
+            // Small elements are their own logarithm
+            let x = element(0.1);
+            let log = log_map(&algebra, &exp_map(&algebra, &x)).unwrap();
+            assert!(!log.branch_cut);
+            assert_abs_diff_eq!(log.coords, x, epsilon = 1e-10);
+
+            // Large elements wrap around, but the logarithm stays in su(n)
+            let u = exp_map(&algebra, &element(3.));
+            let log = log_map(&algebra, &u).unwrap();
+            assert_abs_diff_eq!(exp_map(&algebra, &log.coords), u, epsilon = 1e-9);
+        }
+    }
+
+    // In u(n) this is the principal logarithm
+    let algebra = LieAlgebra::new(gen_u(3, Normalization::Unit));
+    let u = nd::Array2::from_diag(&nd::arr1(&[1., 2., 3.]).mapv(|x| Complex64::new(0., x).exp()));
+    let log = log_map(&algebra, &u).unwrap();
+    assert_abs_diff_eq!(
+        algebra.from_coordinates(&log.coords),
+        nd::Array2::from_diag(&nd::arr1(&[1., 2., 3.]).mapv(|x| Complex64::new(0., x))),
+        epsilon = 1e-10
+    );
+}
+
+#[test]
+fn test_log_map_degenerate() {
+    use std::f64::consts::PI;
+
+    // -1 in SU(2) is a rotation by 2 pi around any axis
+    let algebra = LieAlgebra::new(gen_gellmann(2));
+    let minus_one = -nd::Array2::<Complex64>::eye(2);
+    let log = log_map(&algebra, &minus_one).unwrap();
+    assert!(log.branch_cut);
+    let logs = all_logarithms(&algebra, &minus_one, usize::MAX).unwrap();
+    assert_eq!(logs.len(), 2);
+    assert_abs_diff_eq!(logs[0].clone(), -logs[1].clone(), epsilon = 1e-10);
+    for x in logs.iter() {
+        assert_abs_diff_eq!(exp_map(&algebra, x), minus_one, epsilon = 1e-10);
+        let norm = x.iter().map(|x| x.norm_sqr()).sum::<f64>().sqrt();
+        assert_abs_diff_eq!(norm, PI, epsilon = 1e-10);
+    }
+
+    // The center of SU(3): one of three eigenvalues is shifted by -2 pi
+    let algebra = LieAlgebra::new(gen_gellmann(3));
+    let center = nd::Array2::eye(3) * Complex64::new(0., 2. * PI / 3.).exp();
+    let log = log_map(&algebra, &center).unwrap();
+    assert!(!log.branch_cut);
+    let logs = all_logarithms(&algebra, &center, usize::MAX).unwrap();
+    assert_eq!(logs.len(), 3);
+    for x in logs.iter() {
+        assert_abs_diff_eq!(exp_map(&algebra, x), center, epsilon = 1e-10);
+    }
+
+    // A single eigenvalue -1 has the logarithms i pi and -i pi in u(2)
+    let algebra = LieAlgebra::new(gen_u(2, Normalization::Unit));
+    let u = nd::Array2::from_diag(&nd::arr1(&[
+        Complex64::new(-1., 0.),
+        Complex64::new(0., 1.),
+    ]));
+    assert!(log_map(&algebra, &u).unwrap().branch_cut);
+    assert_eq!(all_logarithms(&algebra, &u, usize::MAX).unwrap().len(), 2);
+}
+
+#[test]
+fn test_all_logarithms_limit() {
+    // -I in SU(6) has C(6, 3) = 20 logarithms on the computed eigenvectors
+    let algebra = LieAlgebra::new(gen_gellmann(6));
+    let minus_one = -nd::Array2::<Complex64>::eye(6);
+    assert_eq!(
+        all_logarithms(&algebra, &minus_one, usize::MAX)
+            .unwrap()
+            .len(),
+        20
+    );
+    let logs = all_logarithms(&algebra, &minus_one, 5).unwrap();
+    assert_eq!(logs.len(), 5);
+    for x in logs.iter() {
+        assert_abs_diff_eq!(exp_map(&algebra, x), minus_one, epsilon = 1e-10);
+    }
+}
+
+#[test]
+fn test_log_map_not_unitary() {
+    let algebra = LieAlgebra::new(gen_gellmann(2));
+    let u = nd::Array2::<Complex64>::eye(2) * Complex64::new(1.1, 0.);
+    let error = log_map(&algebra, &u).unwrap_err();
+    assert!(error.contains("not unitary"));
+    assert!(all_logarithms(&algebra, &u, 1).is_err());
+
+    // Rounding errors well below the tolerance are accepted
+    let u = nd::Array2::<Complex64>::eye(2) * Complex64::new(1. + 1e-12, 0.);
+    assert!(log_map(&algebra, &u).is_ok());
+}
+
+#[test]
+fn test_log_map_symmetric_spectrum() {
+    use rand::SeedableRng;
+
+    // Pairs of eigenvalues symmetric about each of the axes phi that an earlier version used to
+    // separate the eigenvectors through the Hermitian part of e^{-i phi} U
+    let angles: Vec<f64> = [(3. - 5f64.sqrt()) / 2., 5f64.sqrt() - 1., 7f64.sqrt()]
+        .iter()
+        .flat_map(|phi| vec![phi - 0.5, phi + 0.5])
+        .collect();
+    let w = lie::random::haar_unitary(6, &mut rand::rngs::StdRng::seed_from_u64(3));
+    let d = nd::Array1::from_shape_fn(6, |k| Complex64::new(0., angles[k]).exp());
+    let u = (&w * &d).dot(&w.t().mapv(|x| x.conj()));
+
+    let algebra = LieAlgebra::new(gen_u(6, Normalization::Unit));
+    let log = log_map(&algebra, &u).unwrap();
+    assert_abs_diff_eq!(exp_map(&algebra, &log.coords), u, epsilon = 1e-10);
+    let expected = (&w * &d.mapv(|x| Complex64::new(0., x.arg()))).dot(&w.t().mapv(|x| x.conj()));
+    assert_abs_diff_eq!(
+        algebra.from_coordinates(&log.coords),
+        expected,
+        epsilon = 1e-10
+    );
+}
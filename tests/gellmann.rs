@@ -10,9 +10,9 @@ fn check_pauli_structure() {
     // The Pauli matrices satisfy:
     // [sigma_x, sigma_y] = 2sigma_z
 
-    assert_abs_diff_eq!(x.get(&(0usize, 1usize)).unwrap().1.re, 2., epsilon = 1e-8);
-    assert_abs_diff_eq!(x.get(&(1usize, 2usize)).unwrap().1.re, 2., epsilon = 1e-8);
-    assert_abs_diff_eq!(x.get(&(2usize, 0usize)).unwrap().1.re, 2., epsilon = 1e-8);
+    assert_abs_diff_eq!(x.get(0, 1, 2).re, 2., epsilon = 1e-8);
+    assert_abs_diff_eq!(x.get(1, 2, 0).re, 2., epsilon = 1e-8);
+    assert_abs_diff_eq!(x.get(2, 0, 1).re, 2., epsilon = 1e-8);
 }
 
 #[test]
@@ -22,3 +22,93 @@ fn test_gellmann_structure() {
 
     println!("{}", x.len());
 }
+
+#[test]
+fn test_gellmann_d_symbols() {
+    use itertools::Itertools;
+    use num_complex::Complex64;
+
+    // Use the Hermitian Gell-Mann matrices lambda_a, which satisfy
+    // {lambda_a, lambda_b} = 4/3 delta_ab I + 2 d_abc lambda_c
+    let i = Complex64::new(0., 1.);
+    let basis: Vec<_> = gen_gellmann(3).iter().map(|x| x * -i).collect();
+
+    // Position of lambda_1 ... lambda_8 in gen_gellmann(3)
+    let l = [3, 1, 0, 6, 2, 7, 5, 4];
+    let s3 = 3f64.sqrt();
+    let d_abc = [
+        ((1, 1, 8), 1. / s3),
+        ((2, 2, 8), 1. / s3),
+        ((3, 3, 8), 1. / s3),
+        ((8, 8, 8), -1. / s3),
+        ((4, 4, 8), -1. / (2. * s3)),
+        ((5, 5, 8), -1. / (2. * s3)),
+        ((6, 6, 8), -1. / (2. * s3)),
+        ((7, 7, 8), -1. / (2. * s3)),
+        ((1, 4, 6), 0.5),
+        ((1, 5, 7), 0.5),
+        ((2, 5, 6), 0.5),
+        ((3, 4, 4), 0.5),
+        ((3, 5, 5), 0.5),
+        ((2, 4, 7), -0.5),
+        ((3, 6, 6), -0.5),
+        ((3, 7, 7), -0.5),
+    ];
+
+    let mut expected = ndarray::Array3::<Complex64>::zeros((8, 8, 8));
+    for ((a, b, c), d) in d_abc.iter() {
+        for p in [l[a - 1], l[b - 1], l[c - 1]].iter().permutations(3) {
+            expected[[*p[0], *p[1], *p[2]]] = Complex64::new(2. * d, 0.);
+        }
+    }
+
+    let x = find_d_tensor(&basis);
+    for (e, got) in expected.iter().zip(x.d.to_dense().iter()) {
+        assert_abs_diff_eq!(e.re, got.re, epsilon = 1e-8);
+        assert_abs_diff_eq!(e.im, got.im, epsilon = 1e-8);
+    }
+
+    let identity = ndarray::Array2::<f64>::eye(8) * 4. / 3.;
+    for (e, got) in identity.iter().zip(x.identity.iter()) {
+        assert_abs_diff_eq!(*e, got.re, epsilon = 1e-8);
+        assert_abs_diff_eq!(0., got.im, epsilon = 1e-8);
+    }
+}
+
+#[test]
+fn test_gellmann_anticommutator() {
+    use approx::AbsDiffEq;
+    use num_complex::Complex64;
+
+    let g = gen_gellmann(3);
+    let d = find_d_tensor(&g);
+
+    let n = g.len();
+    for a in 0..n {
+        for b in 0..n {
+            let mut l_a = ndarray::Array1::zeros(n);
+            let mut l_b = ndarray::Array1::zeros(n);
+            l_a[a] = Complex64::new(1., 0.);
+            l_b[b] = Complex64::new(1., 0.);
+
+            let res = su_anticommutator(&l_a, &l_b, &d, &g);
+            let expected = g[a].dot(&g[b]) + g[b].dot(&g[a]);
+            assert!(res.abs_diff_eq(&expected, 1e-8));
+        }
+    }
+}
+
+#[test]
+fn test_closed_form_structure_constants() {
+    for d in 2..6 {
+        let g = gen_gellmann(d);
+
+        let f = structure_constants(d);
+        let expected = find_structure_constants(&g);
+        assert_abs_diff_eq!(f.to_dense(), expected.to_dense(), epsilon = 1e-8);
+
+        let d_abc = d_coefficients(d);
+        let expected = find_d_coefficients(&g);
+        assert_abs_diff_eq!(d_abc.to_dense(), expected.to_dense(), epsilon = 1e-8);
+    }
+}
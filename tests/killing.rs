@@ -0,0 +1,100 @@
+use approx::assert_abs_diff_eq;
+use lie::gellmann::*;
+use lie::killing::*;
+use lie::lie_algebra::*;
+use lie::su2::*;
+use lie::sylvester::*;
+use ndarray as nd;
+use num_complex::Complex64;
+
+fn to_complex(basis: &[nd::Array2<f64>]) -> Vec<nd::Array2<Complex64>> {
+    basis
+        .iter()
+        .map(|x| x.mapv(|x| Complex64::new(x, 0.)))
+        .collect()
+}
+
+#[test]
+fn test_su2_killing_form() {
+    let f = find_structure_constants(&gen_gellmann(2));
+    let g = killing_form(&f);
+
+    // f_abc = 2 epsilon_abc, so g_ab = -8 delta_ab
+    let expected = nd::Array2::<Complex64>::eye(3) * -8.;
+    assert_abs_diff_eq!(g, expected, epsilon = 1e-8);
+
+    assert!(is_semisimple(&f));
+    assert!(is_compact(&f));
+}
+
+#[test]
+fn test_killing_form_is_proportional_to_trace_form() {
+    // For su(n) in the fundamental representation the Killing form is 2n Tr(T_a T_b), also in a
+    // basis that is not orthogonal
+    let mut basis = gen_gellmann(3);
+    basis[0] = &basis[0] + &basis[4];
+    basis[6] = &basis[6] - &(&basis[2] * 0.5);
+
+    let g = killing_form(&find_structure_constants(&basis));
+    assert_abs_diff_eq!(g, trace_form(&basis) * 6., epsilon = 1e-8);
+
+    let algebra = LieAlgebra::new(basis);
+    assert_abs_diff_eq!(*algebra.killing_form(), g, epsilon = 1e-8);
+    // Computed once and cached
+    assert!(std::ptr::eq(algebra.killing_form(), algebra.killing_form()));
+}
+
+#[test]
+fn test_non_compact_and_complex_bases() {
+    // sl(2, R) is semisimple but not compact
+    let f = find_structure_constants(&to_complex(&gen_sl2(0.5)));
+    assert!(is_semisimple(&f));
+    assert!(!is_compact(&f));
+
+    // The clock and shift basis is complex
+    let f = find_structure_constants(&gen_sylvester(3));
+    assert!(is_semisimple(&f));
+    assert!(!is_compact(&f));
+}
+
+#[test]
+fn test_not_semisimple() {
+    // The Heisenberg algebra is nilpotent, so its Killing form vanishes
+    let mut x = nd::Array2::<f64>::zeros((3, 3));
+    let mut y = x.clone();
+    let mut z = x.clone();
+    x[[0, 1]] = 1.;
+    y[[1, 2]] = 1.;
+    z[[0, 2]] = 1.;
+
+    let f = find_structure_constants(&to_complex(&[x, y, z]));
+    assert_abs_diff_eq!(
+        killing_form(&f),
+        nd::Array2::<Complex64>::zeros((3, 3)),
+        epsilon = 1e-8
+    );
+    assert!(!is_semisimple(&f));
+}
+
+#[test]
+fn test_totally_antisymmetric() {
+    let mut basis = gen_gellmann(3);
+    basis[1] = &basis[1] + &(&basis[3] * 2.);
+
+    let f = find_structure_constants(&basis);
+    let g = killing_form(&f);
+    let f_lower = lower_index(&f, &g);
+
+    let dense = f_lower.to_dense();
+    for ((a, b, c), x) in dense.indexed_iter() {
+        assert_abs_diff_eq!(*x, -dense[[b, a, c]], epsilon = 1e-8);
+        assert_abs_diff_eq!(*x, -dense[[a, c, b]], epsilon = 1e-8);
+        assert_abs_diff_eq!(*x, dense[[b, c, a]], epsilon = 1e-8);
+    }
+
+    assert_abs_diff_eq!(
+        raise_index(&f_lower, &g).to_dense(),
+        f.to_dense(),
+        epsilon = 1e-8
+    );
+}
@@ -0,0 +1,114 @@
+use approx::AbsDiffEq;
+use lie::gellmann::*;
+use lie::lie_algebra::*;
+use lie::sylvester::*;
+use ndarray as nd;
+use num_complex::Complex64;
+
+fn coords(x: &[f64]) -> nd::Array1<Complex64> {
+    x.iter().map(|x| Complex64::new(*x, 0.5 * x)).collect()
+}
+
+#[test]
+fn test_coordinates_round_trip() {
+    for basis in &[gen_gellmann(3), gen_sylvester(3)] {
+        let algebra = LieAlgebra::new(basis.clone());
+        let x = coords(&[0.1, -0.4, 1.2, 0.3, 0., 2.1, -0.7, 0.9]);
+
+        let m = algebra.from_coordinates(&x);
+        assert!(algebra.to_coordinates(&m).abs_diff_eq(&x, 1e-8));
+    }
+}
+
+#[test]
+fn test_bracket_and_anticommutator() {
+    let mut basis = gen_gellmann(3);
+    basis[2] = &basis[2] + &basis[5];
+    let algebra = LieAlgebra::new(basis);
+
+    let x = coords(&[0.1, -0.4, 1.2, 0.3, 0., 2.1, -0.7, 0.9]);
+    let y = coords(&[1.0, 0.2, -0.3, 0.8, 0.5, -1.1, 0.4, 0.]);
+    let m_x = algebra.from_coordinates(&x);
+    let m_y = algebra.from_coordinates(&y);
+
+    let commutator = m_x.dot(&m_y) - m_y.dot(&m_x);
+    let bracket = algebra.from_coordinates(&algebra.bracket(&x, &y));
+    assert!(bracket.abs_diff_eq(&commutator, 1e-8));
+
+    let anticommutator = m_x.dot(&m_y) + m_y.dot(&m_x);
+    let (c, z) = algebra.anticommutator(&x, &y);
+    let result = algebra.from_coordinates(&z) + nd::Array2::<Complex64>::eye(3) * c;
+    assert!(result.abs_diff_eq(&anticommutator, 1e-8));
+}
+
+#[test]
+fn test_metric() {
+    let algebra = LieAlgebra::new(gen_gellmann(2));
+
+    // The generators are i sigma_k, so Tr(T_a T_b) = -2 delta_ab
+    let expected = nd::Array2::<Complex64>::eye(3) * -2.;
+    assert!(algebra.metric().abs_diff_eq(&expected, 1e-8));
+    assert_eq!(algebra.dim(), 3);
+    assert_eq!(algebra.matrix_dim(), 2);
+}
+
+fn check_commutators(basis: &[nd::Array2<Complex64>]) {
+    let f = find_structure_constants(basis);
+    for (a, t_a) in basis.iter().enumerate() {
+        for (b, t_b) in basis.iter().enumerate() {
+            let mut expected = t_a.dot(t_b) - t_b.dot(t_a);
+            for (c, f_abc) in f.pair(a, b) {
+                expected.scaled_add(-*f_abc, &basis[*c]);
+            }
+            assert!(expected.abs_diff_eq(&nd::Array2::zeros(t_a.raw_dim()), 1e-8));
+        }
+    }
+}
+
+#[test]
+fn test_structure_constants_orthogonal_basis() {
+    check_commutators(&gen_gellmann(7));
+    check_commutators(&gen_sylvester(5));
+}
+
+#[test]
+fn test_structure_constants_general_basis() {
+    // Mixing the elements of the basis breaks orthogonality, which needs the QR fallback
+    let mut basis = gen_gellmann(4);
+    for a in 1..basis.len() {
+        basis[a] = &basis[a] + &(&basis[a - 1] * 0.5);
+    }
+    check_commutators(&basis);
+}
+
+#[test]
+#[should_panic(expected = "The basis is not linearly independent")]
+fn test_structure_constants_dependent_basis() {
+    // A repeated element makes the basis rank deficient, which would divide by zero in the QR
+    let mut basis = gen_gellmann(3);
+    basis.push(&basis[0] + &basis[1]);
+    find_structure_constants(&basis);
+}
+
+#[test]
+#[should_panic(expected = "The basis is not linearly independent")]
+fn test_structure_constants_zero_element() {
+    let mut basis = gen_gellmann(3);
+    basis[2] = nd::Array2::zeros((3, 3));
+    find_structure_constants(&basis);
+}
+
+#[test]
+#[should_panic(expected = "The basis is not closed under the anticommutator: {T_0, T_0}")]
+fn test_d_tensor_not_closed() {
+    // J_x^2 is neither antisymmetric nor proportional to the identity
+    let so3 = lie::classical::gen_so(3, lie::classical::Normalization::Unit);
+    find_d_tensor(&so3);
+}
+
+#[test]
+fn test_d_tensor_empty_basis() {
+    let d = find_d_tensor(&[]);
+    assert_eq!(d.d.dim(), 0);
+    assert_eq!(d.identity.shape(), &[0, 0]);
+}
@@ -0,0 +1,143 @@
+use approx::assert_abs_diff_eq;
+use lie::gellmann::*;
+use lie::lie_algebra::*;
+use lie::random::*;
+use lie::real_forms::*;
+use ndarray as nd;
+use ndarray_linalg::*;
+use num_complex::Complex64;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+const SAMPLES: usize = 20000;
+
+// The Monte Carlo average of f over the samples
+fn average<F: FnMut() -> f64>(mut f: F) -> f64 {
+    (0..SAMPLES).map(|_| f()).sum::<f64>() / SAMPLES as f64
+}
+
+fn trace_power(u: &nd::Array2<Complex64>, k: usize) -> Complex64 {
+    let mut res = nd::Array2::eye(u.shape()[0]);
+    for _ in 0..k {
+        res = res.dot(u);
+    }
+    res.diag().sum()
+}
+
+#[test]
+fn test_unitary() {
+    let mut rng = StdRng::seed_from_u64(17);
+    let n = 3;
+    let u = haar_unitary(n, &mut rng);
+    assert_abs_diff_eq!(
+        u.t().mapv(|x| x.conj()).dot(&u),
+        nd::Array2::eye(n),
+        epsilon = 1e-12
+    );
+
+    // E|U_11|^2 = 1/n, E|U_11|^4 = 2/(n(n+1)) and E|U_11|^2 |U_12|^2 = 1/(n(n+1))
+    let moments: Vec<f64> = (0..SAMPLES)
+        .flat_map(|_| {
+            let u = haar_unitary(n, &mut rng);
+            let (a, b) = (u[[0, 0]].norm_sqr(), u[[0, 1]].norm_sqr());
+            vec![
+                a,
+                a * a,
+                a * b,
+                trace_power(&u, 1).norm_sqr(),
+                trace_power(&u, 2).re,
+            ]
+        })
+        .collect();
+    let mean = |k: usize| moments.iter().skip(k).step_by(5).sum::<f64>() / SAMPLES as f64;
+    let n = n as f64;
+    assert_abs_diff_eq!(mean(0), 1. / n, epsilon = 0.01);
+    assert_abs_diff_eq!(mean(1), 2. / (n * (n + 1.)), epsilon = 0.01);
+    assert_abs_diff_eq!(mean(2), 1. / (n * (n + 1.)), epsilon = 0.01);
+    // E|Tr U|^2 = 1 and E Tr U^2 = 0
+    assert_abs_diff_eq!(mean(3), 1., epsilon = 0.05);
+    assert_abs_diff_eq!(mean(4), 0., epsilon = 0.05);
+}
+
+#[test]
+fn test_special_unitary() {
+    let mut rng = StdRng::seed_from_u64(3);
+    for n in 2..5 {
+        let u = haar_special_unitary(n, &mut rng);
+        assert_abs_diff_eq!(u.det().unwrap(), Complex64::new(1., 0.), epsilon = 1e-10);
+    }
+    // E|Tr U|^2 = 1 on SU(n) for n > 1, while E|Tr U|^4 = 2 for n > 2
+    let trace2 = average(|| trace_power(&haar_special_unitary(3, &mut rng), 1).norm_sqr());
+    assert_abs_diff_eq!(trace2, 1., epsilon = 0.05);
+    let trace4 = average(|| {
+        trace_power(&haar_special_unitary(3, &mut rng), 1)
+            .norm_sqr()
+            .powi(2)
+    });
+    assert_abs_diff_eq!(trace4, 2., epsilon = 0.15);
+}
+
+#[test]
+fn test_orthogonal() {
+    let mut rng = StdRng::seed_from_u64(5);
+    let n = 4;
+    let o = haar_orthogonal(n, &mut rng);
+    assert_abs_diff_eq!(o.t().dot(&o), nd::Array2::eye(n), epsilon = 1e-12);
+
+    // E O_11^2 = 1/n and E O_11^4 = 3/(n(n+2))
+    let nf = n as f64;
+    let m2 = average(|| haar_orthogonal(n, &mut rng)[[0, 0]].powi(2));
+    assert_abs_diff_eq!(m2, 1. / nf, epsilon = 0.01);
+    let m4 = average(|| haar_orthogonal(n, &mut rng)[[0, 0]].powi(4));
+    assert_abs_diff_eq!(m4, 3. / (nf * (nf + 2.)), epsilon = 0.01);
+    // Both determinants are equally likely, and E Tr O^2 = 1 for the real representation
+    let det = average(|| haar_orthogonal(n, &mut rng).det().unwrap());
+    assert_abs_diff_eq!(det, 0., epsilon = 0.05);
+    let trace = average(|| {
+        let o = haar_orthogonal(n, &mut rng);
+        o.dot(&o).diag().sum()
+    });
+    assert_abs_diff_eq!(trace, 1., epsilon = 0.05);
+
+    let so = haar_special_orthogonal(n, &mut rng);
+    assert_abs_diff_eq!(so.det().unwrap(), 1., epsilon = 1e-10);
+    let trace = average(|| haar_special_orthogonal(3, &mut rng).diag().sum());
+    assert_abs_diff_eq!(trace, 0., epsilon = 0.05);
+}
+
+#[test]
+fn test_symplectic() {
+    let mut rng = StdRng::seed_from_u64(11);
+    let n = 2;
+    let u = haar_symplectic(n, &mut rng);
+    assert_abs_diff_eq!(
+        u.t().mapv(|x| x.conj()).dot(&u),
+        nd::Array2::eye(2 * n),
+        epsilon = 1e-12
+    );
+    let j = symplectic_form(n);
+    assert_abs_diff_eq!(u.t().dot(&j).dot(&u), j, epsilon = 1e-12);
+
+    // E|Tr U|^2 = 1, and E Tr U^2 = -1 for the quaternionic representation
+    let trace2 = average(|| trace_power(&haar_symplectic(n, &mut rng), 1).norm_sqr());
+    assert_abs_diff_eq!(trace2, 1., epsilon = 0.05);
+    let trace = average(|| trace_power(&haar_symplectic(n, &mut rng), 2).re);
+    assert_abs_diff_eq!(trace, -1., epsilon = 0.05);
+}
+
+#[test]
+fn test_seeded_and_elements() {
+    let sample = |seed| haar_unitary(4, &mut StdRng::seed_from_u64(seed));
+    assert_eq!(sample(7), sample(7));
+    assert_ne!(sample(7), sample(8));
+
+    // The coordinates of a random element of su(3) are real with unit variance
+    let algebra = LieAlgebra::new(gen_gellmann(3));
+    let mut rng = StdRng::seed_from_u64(13);
+    let variance = average(|| {
+        let x = random_element(&algebra, &mut rng);
+        assert!(x.iter().all(|x| x.im == 0.));
+        x.iter().map(|x| x.norm_sqr()).sum::<f64>() / algebra.dim() as f64
+    });
+    assert_abs_diff_eq!(variance, 1., epsilon = 0.02);
+}
@@ -0,0 +1,158 @@
+use approx::assert_abs_diff_eq;
+use lie::classical::{gen_sp, Normalization};
+use lie::killing::*;
+use lie::lie_algebra::*;
+use lie::real_forms::*;
+use lie::verify::*;
+use ndarray as nd;
+use num_complex::Complex64;
+
+// exp(x) by scaling and squaring of a Taylor series
+fn expm(x: &nd::Array2<Complex64>) -> nd::Array2<Complex64> {
+    let n = x.shape()[0];
+    let norm: f64 = x.iter().map(|x| x.norm()).sum();
+    let squarings = norm.log2().ceil().max(0.) as i32 + 4;
+    let x = x * Complex64::new(0.5f64.powi(squarings), 0.);
+
+    let mut res = nd::Array2::eye(n);
+    let mut term = nd::Array2::eye(n);
+    for k in 1..20 {
+        term = term.dot(&x) / Complex64::new(k as f64, 0.);
+        res += &term;
+    }
+    for _ in 0..squarings {
+        res = res.dot(&res);
+    }
+    res
+}
+
+// A combination of basis elements with fixed, unrelated coefficients
+fn element(basis: &[nd::Array2<Complex64>]) -> nd::Array2<Complex64> {
+    let mut x = nd::Array2::zeros(basis[0].raw_dim());
+    for (k, b) in basis.iter().enumerate() {
+        x.scaled_add(Complex64::new(0.3 + 0.17 * k as f64, 0.), b);
+    }
+    x
+}
+
+#[test]
+fn test_so_pq() {
+    for (p, q) in [(3, 1), (2, 2), (4, 1), (2, 1)].iter() {
+        let basis = gen_so_pq(*p, *q);
+        let n = p + q;
+        assert_eq!(basis.len(), n * (n - 1) / 2);
+        assert!(verify_lie_algebra(&basis).is_lie_algebra());
+
+        let eta = signature_metric(*p, *q);
+        for x in basis.iter() {
+            assert_abs_diff_eq!(x.t().dot(&eta) + eta.dot(x), nd::Array2::zeros((n, n)));
+        }
+
+        // Rotations are compact, boosts are not
+        let f = find_structure_constants(&basis);
+        let rotations = p * (p - 1) / 2 + q * (q - 1) / 2;
+        assert_eq!(signature(&killing_form(&f)), (p * q, rotations, 0));
+
+        let g = expm(&element(&basis));
+        assert!(preserves_form(&g, &eta));
+        assert!(!preserves_form(&g, &nd::Array2::eye(n)));
+    }
+}
+
+#[test]
+fn test_su_pq() {
+    for (p, q) in [(1, 1), (2, 1), (2, 2)].iter() {
+        let basis = gen_su_pq(*p, *q);
+        let n = p + q;
+        assert_eq!(basis.len(), n * n - 1);
+        assert!(verify_lie_algebra(&basis).is_lie_algebra());
+
+        let eta = signature_metric(*p, *q);
+        for x in basis.iter() {
+            let x_dagger = x.t().mapv(|x| x.conj());
+            assert_abs_diff_eq!(x_dagger.dot(&eta) + eta.dot(x), nd::Array2::zeros((n, n)));
+        }
+
+        // The noncompact part has dimension 2pq
+        let f = find_structure_constants(&basis);
+        assert_eq!(
+            signature(&killing_form(&f)),
+            (2 * p * q, n * n - 1 - 2 * p * q, 0)
+        );
+
+        let g = expm(&element(&basis));
+        assert!(preserves_form(&g, &eta));
+    }
+}
+
+#[test]
+fn test_sp_real() {
+    let n = 2;
+    let basis = gen_sp(n, Normalization::Unit);
+    let j = symplectic_form(n);
+    for x in basis.iter() {
+        assert_abs_diff_eq!(x.t().dot(&j) + j.dot(x), nd::Array2::zeros((2 * n, 2 * n)));
+    }
+
+    // The maximal compact subalgebra of sp(4, R) is u(2)
+    let f = find_structure_constants(&basis);
+    assert_eq!(signature(&killing_form(&f)), (6, 4, 0));
+
+    let g = expm(&element(&basis));
+    assert!(preserves_form(&g, &j));
+}
+
+#[test]
+fn test_lorentz() {
+    let basis = gen_lorentz();
+    let f = find_structure_constants(&basis);
+
+    let (jx, jy, jz, kx, ky, kz) = (0, 1, 2, 3, 4, 5);
+    let check = |a: usize, b: usize, c: usize, value: f64| {
+        assert_abs_diff_eq!(f.get(a, b, c), Complex64::new(value, 0.), epsilon = 1e-10);
+        assert_eq!(f.pair(a, b).len(), 1);
+    };
+    check(jx, jy, jz, 1.);
+    check(jy, jz, jx, 1.);
+    check(jx, ky, kz, 1.);
+    check(kx, jy, kz, 1.);
+    check(kx, ky, jz, -1.);
+    check(ky, kz, jx, -1.);
+
+    assert_eq!(signature(&killing_form(&f)), (3, 3, 0));
+
+    // so(3, 1) from gen_so_pq is the same real form
+    let so31 = LieAlgebra::new(gen_so_pq(3, 1));
+    assert_eq!(signature(so31.killing_form()), signature(&killing_form(&f)));
+
+    // A boost along x with rapidity 0.7 and a rotation preserve the Minkowski metric
+    let eta = signature_metric(3, 1);
+    let boost = expm(&(&basis[kx] * Complex64::new(0.7, 0.)));
+    assert_abs_diff_eq!(boost[[0, 0]].re, 0.7f64.cosh(), epsilon = 1e-10);
+    assert_abs_diff_eq!(boost[[0, 3]].re, 0.7f64.sinh(), epsilon = 1e-10);
+    assert!(preserves_form(&boost, &eta));
+    let rotation = expm(&(&basis[jz] * Complex64::new(1.3, 0.)));
+    assert!(preserves_form(&rotation.dot(&boost), &eta));
+}
+
+#[test]
+fn test_so21_su11() {
+    let so21 = find_structure_constants(&gen_so21());
+    let su11 = find_structure_constants(&gen_su11());
+    assert_abs_diff_eq!(so21.to_dense(), su11.to_dense(), epsilon = 1e-10);
+
+    assert_eq!(signature(&killing_form(&su11)), (2, 1, 0));
+
+    let g = expm(&element(&gen_su11()));
+    let sigma_z = signature_metric(1, 1);
+    assert!(preserves_form(&g, &sigma_z));
+    let g = expm(&element(&gen_so21()));
+    assert!(preserves_form(&g, &signature_metric(2, 1)));
+}
+
+#[test]
+fn test_so_pq_small() {
+    assert!(gen_so_pq(0, 0).is_empty());
+    assert!(gen_so_pq(1, 0).is_empty());
+    assert_eq!(gen_so_pq(1, 1).len(), 1);
+}
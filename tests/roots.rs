@@ -0,0 +1,86 @@
+use approx::assert_abs_diff_eq;
+use lie::gellmann::*;
+use lie::lie_algebra::*;
+use lie::roots::*;
+use lie::sylvester::*;
+use ndarray as nd;
+
+fn cartan_matrix(roots: &RootSystem) -> nd::Array2<f64> {
+    let r = roots.rank();
+    nd::Array2::from_shape_fn((r, r), |(i, j)| {
+        roots.inner_product(&roots.simple_roots[i], &roots.coroots[j])
+    })
+}
+
+fn check_su_n(roots: &RootSystem, n: usize) {
+    assert_eq!(roots.rank(), n - 1);
+    assert_eq!(roots.roots.len(), n * (n - 1));
+    assert_eq!(roots.positive_roots.len(), n * (n - 1) / 2);
+    assert_eq!(roots.simple_roots.len(), n - 1);
+
+    // Every root of su(n) has the same length
+    let length = roots.inner_product(&roots.roots[0], &roots.roots[0]);
+    for alpha in &roots.roots {
+        assert_abs_diff_eq!(roots.inner_product(alpha, alpha), length, epsilon = 1e-8);
+    }
+
+    // The Cartan matrix of A_{n-1} has 2 on the diagonal and -1 or 0 elsewhere
+    let a = cartan_matrix(roots);
+    for ((i, j), x) in a.indexed_iter() {
+        if i == j {
+            assert_abs_diff_eq!(*x, 2., epsilon = 1e-8);
+        } else {
+            assert!(x.abs() < 1e-8 || (x + 1.).abs() < 1e-8);
+        }
+    }
+
+    // The highest root is the sum of all simple roots
+    let coefficients = roots.simple_root_coefficients(&roots.highest_root);
+    assert_abs_diff_eq!(coefficients, nd::Array1::ones(n - 1), epsilon = 1e-8);
+}
+
+#[test]
+fn test_gellmann_cartan_subalgebra() {
+    let algebra = LieAlgebra::new(gen_gellmann(3));
+    let cartan = cartan_subalgebra(&algebra);
+
+    // The diagonal generators are elements 0 and 4
+    assert_eq!(cartan.len(), 2);
+    assert_abs_diff_eq!(cartan[0][0].re, 1., epsilon = 1e-8);
+    assert_abs_diff_eq!(cartan[1][4].re, 1., epsilon = 1e-8);
+}
+
+#[test]
+fn test_su_n_root_systems() {
+    for n in 2..5 {
+        let roots = root_system(&LieAlgebra::new(gen_gellmann(n)));
+        check_su_n(&roots, n);
+    }
+}
+
+#[test]
+fn test_root_vectors() {
+    let algebra = LieAlgebra::new(gen_gellmann(3));
+    let roots = root_system(&algebra);
+
+    // [H_i, E_alpha] = alpha(H_i) E_alpha, where the roots of the compact basis are -i alpha(H_i)
+    let i = num_complex::Complex64::new(0., 1.);
+    for (alpha, e) in roots.roots.iter().zip(&roots.root_vectors) {
+        for (h, alpha_h) in roots.cartan.iter().zip(alpha) {
+            assert_abs_diff_eq!(algebra.bracket(h, e), e * (i * *alpha_h), epsilon = 1e-8);
+        }
+    }
+}
+
+#[test]
+fn test_generic_bases() {
+    // No two elements of the mixed basis commute, so the Cartan subalgebra is a centralizer
+    let basis = gen_gellmann(3);
+    let mixed: Vec<_> = (0..basis.len())
+        .map(|a| &basis[a] + &(&basis[(a + 1) % basis.len()] * 0.3))
+        .collect();
+    check_su_n(&root_system(&LieAlgebra::new(mixed)), 3);
+
+    // The clock and shift Cartan subalgebra has complex roots
+    check_su_n(&root_system(&LieAlgebra::new(gen_sylvester(3))), 3);
+}
@@ -0,0 +1,208 @@
+use approx::assert_abs_diff_eq;
+use lie::euler::*;
+use lie::exponential::*;
+use lie::random::*;
+use lie::rotation::*;
+use lie::su2::*;
+use ndarray as nd;
+use num_complex::Complex64;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::f64::consts::PI;
+
+fn assert_quaternion_eq(a: &Quaternion, b: &Quaternion, epsilon: f64) {
+    let diff = [a.w - b.w, a.x - b.x, a.y - b.y, a.z - b.z];
+    assert!(diff.iter().all(|d| d.abs() < epsilon), "{:?} != {:?}", a, b);
+}
+
+fn pauli() -> [nd::Array2<Complex64>; 3] {
+    let [s_z, s_x, i_s_y] = gen_su2(0.5);
+    let i = Complex64::new(0., 1.);
+    [&s_x * 2., &i_s_y * (-i * 2.), &s_z * 2.]
+}
+
+#[test]
+fn test_su2() {
+    // The quaternion is exp(-i theta n.S) with the spin 1/2 generators of gen_su2
+    let [s_z, s_x, i_s_y] = gen_su2(0.5);
+    let (axis, angle) = ([0.3, -1.2, 0.5], 2.3);
+    let norm = (0.09f64 + 1.44 + 0.25).sqrt();
+    let generator = &s_x * Complex64::new(0., -angle * axis[0] / norm)
+        + &i_s_y * Complex64::new(-angle * axis[1] / norm, 0.)
+        + &s_z * Complex64::new(0., -angle * axis[2] / norm);
+    let q = Quaternion::from_axis_angle(axis, angle);
+    assert_abs_diff_eq!(q.to_su2(), expm(&generator), epsilon = 1e-12);
+
+    // Euler angles are the product of rotations around z and y
+    let (alpha, beta, gamma) = (0.4, 2.2, -1.3);
+    let euler = Quaternion::from_axis_angle([0., 0., 1.], alpha)
+        * Quaternion::from_axis_angle([0., 1., 0.], beta)
+        * Quaternion::from_axis_angle([0., 0., 1.], gamma);
+    assert_abs_diff_eq!(
+        euler.to_su2(),
+        su2_from_euler(alpha, beta, gamma, EulerConvention::Zyz),
+        epsilon = 1e-12
+    );
+
+    // Lossless round trips, including both signs
+    let mut rng = StdRng::seed_from_u64(24);
+    for _ in 0..50 {
+        let u = haar_special_unitary(2, &mut rng);
+        let q = Quaternion::from_su2(&u);
+        assert_abs_diff_eq!(q.norm(), 1., epsilon = 1e-12);
+        assert_abs_diff_eq!(q.to_su2(), u, epsilon = 1e-14);
+        assert_abs_diff_eq!((-q).to_su2(), -u, epsilon = 1e-14);
+    }
+}
+
+#[test]
+fn test_so3() {
+    let sigma = pauli();
+    let mut rng = StdRng::seed_from_u64(3);
+    for _ in 0..50 {
+        let u = haar_special_unitary(2, &mut rng);
+        let u_dagger = u.t().mapv(|x| x.conj());
+        let r = su2_to_so3(&u);
+
+        // U sigma_k U^dagger = sum_j R_jk sigma_j
+        for k in 0..3 {
+            let rotated = u.dot(&sigma[k]).dot(&u_dagger);
+            let expected = (0..3).fold(nd::Array2::zeros((2, 2)), |acc, j| {
+                acc + &sigma[j] * Complex64::new(r[[j, k]], 0.)
+            });
+            assert_abs_diff_eq!(rotated, expected, epsilon = 1e-12);
+        }
+
+        // The lift recovers one of the two preimages, or exactly U with U as reference
+        let lifted = so3_to_su2(&r, None);
+        assert!(
+            (&lifted - &u).iter().all(|x| x.norm() < 1e-10)
+                || (&lifted + &u).iter().all(|x| x.norm() < 1e-10)
+        );
+        assert!(lifted[[0, 0]].re >= 0.);
+        assert_abs_diff_eq!(so3_to_su2(&r, Some(&u)), u, epsilon = 1e-10);
+        assert_abs_diff_eq!(so3_to_su2(&r, Some(&-&u)), -&u, epsilon = 1e-10);
+    }
+
+    // Rotation by pi around an axis, where the trace is -1
+    let q = Quaternion::from_axis_angle([1., 1., 0.], PI);
+    let lifted = Quaternion::from_rotation_matrix(&q.to_rotation_matrix(), Some(&q));
+    assert_quaternion_eq(&lifted, &q, 1e-12);
+}
+
+#[test]
+fn test_axis_angle() {
+    for &(axis, angle) in [
+        ([0., 0., 1.], 0.),
+        ([1., 0., 0.], 0.5),
+        ([0.2, -0.7, 0.1], 3.),
+        ([0., 1., 0.], PI),
+        ([-1., 2., 3.], 5.),
+    ]
+    .iter()
+    {
+        let q = Quaternion::from_axis_angle(axis, angle);
+        let (res_axis, res_angle) = q.to_axis_angle();
+        assert_abs_diff_eq!(res_angle, angle, epsilon = 1e-12);
+        let norm = axis.iter().map(|x| x * x).sum::<f64>().sqrt();
+        for i in 0..3 {
+            assert_abs_diff_eq!(res_axis[i], axis[i] / norm, epsilon = 1e-12);
+        }
+        assert_quaternion_eq(&Quaternion::from_axis_angle(res_axis, res_angle), &q, 1e-12);
+    }
+
+    // The angle 2 pi is -1 in SU(2) and the identity in SO(3)
+    let q = Quaternion::from_axis_angle([1., 0., 0.], 2. * PI);
+    assert_abs_diff_eq!(q.to_axis_angle().1, 2. * PI, epsilon = 1e-12);
+    assert_abs_diff_eq!(
+        q.to_su2(),
+        -nd::Array2::<Complex64>::eye(2),
+        epsilon = 1e-12
+    );
+    assert_abs_diff_eq!(q.to_rotation_matrix(), nd::Array2::eye(3), epsilon = 1e-12);
+}
+
+#[test]
+fn test_lift_path() {
+    // A full turn around an axis goes from 1 to -1 in SU(2)
+    let steps = 100;
+    let rotations: Vec<_> = (0..=steps)
+        .map(|i| {
+            Quaternion::from_axis_angle([1., -1., 2.], 2. * PI * i as f64 / steps as f64)
+                .to_rotation_matrix()
+        })
+        .collect();
+    let path = lift_path(&rotations);
+    for (i, q) in path.iter().enumerate() {
+        let expected =
+            Quaternion::from_axis_angle([1., -1., 2.], 2. * PI * i as f64 / steps as f64);
+        assert_quaternion_eq(q, &expected, 1e-10);
+    }
+    assert_quaternion_eq(&path[steps], &-Quaternion::identity(), 1e-10);
+
+    // Without a reference, the sign jumps at the angle pi
+    let last = Quaternion::from_rotation_matrix(&rotations[steps], None);
+    assert_quaternion_eq(&last, &Quaternion::identity(), 1e-10);
+}
+
+#[test]
+fn test_composition() {
+    let mut rng = StdRng::seed_from_u64(9);
+    for _ in 0..20 {
+        let a = Quaternion::from_su2(&haar_special_unitary(2, &mut rng));
+        let b = Quaternion::from_su2(&haar_special_unitary(2, &mut rng));
+        assert_abs_diff_eq!(
+            (a * b).to_su2(),
+            a.to_su2().dot(&b.to_su2()),
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            (a * b).to_rotation_matrix(),
+            a.to_rotation_matrix().dot(&b.to_rotation_matrix()),
+            epsilon = 1e-12
+        );
+        assert_quaternion_eq(&(a * a.conjugate()), &Quaternion::identity(), 1e-12);
+    }
+}
+
+#[test]
+fn test_slerp() {
+    let a = Quaternion::from_axis_angle([0., 0., 1.], 0.3);
+    let b = Quaternion::from_axis_angle([0., 0., 1.], 2.1);
+    assert_quaternion_eq(&a.slerp(&b, 0.), &a, 1e-12);
+    assert_quaternion_eq(&a.slerp(&b, 1.), &b, 1e-12);
+    for &t in [0.25, 0.5, 0.8].iter() {
+        let expected = Quaternion::from_axis_angle([0., 0., 1.], 0.3 + 1.8 * t);
+        assert_quaternion_eq(&a.slerp(&b, t), &expected, 1e-12);
+    }
+
+    // Constant angular velocity between arbitrary rotations
+    let mut rng = StdRng::seed_from_u64(5);
+    let a = Quaternion::from_su2(&haar_special_unitary(2, &mut rng));
+    let b = Quaternion::from_su2(&haar_special_unitary(2, &mut rng));
+    let omega = a.dot(&b).acos();
+    for &t in [0.1, 0.5, 0.9].iter() {
+        let q = a.slerp(&b, t);
+        assert_abs_diff_eq!(q.norm(), 1., epsilon = 1e-12);
+        assert_abs_diff_eq!(a.dot(&q).acos(), t * omega, epsilon = 1e-10);
+        assert_abs_diff_eq!(q.dot(&b).acos(), (1. - t) * omega, epsilon = 1e-10);
+    }
+
+    // Nearly equal quaternions
+    let c = Quaternion::from_axis_angle([0., 1., 0.], 1e-12);
+    assert_quaternion_eq(&Quaternion::identity().slerp(&c, 0.5), &c, 1e-12);
+
+    // Opposite quaternions, which are the same rotation, at constant angular velocity
+    let a = Quaternion::from_su2(&haar_special_unitary(2, &mut rng));
+    assert_quaternion_eq(&a.slerp(&-a, 0.), &a, 1e-12);
+    assert_quaternion_eq(&a.slerp(&-a, 1.), &-a, 1e-12);
+    for &t in [0.25, 0.5, 0.75].iter() {
+        let q = a.slerp(&-a, t);
+        assert_abs_diff_eq!(q.norm(), 1., epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            a.dot(&q).clamp(-1., 1.).acos(),
+            t * std::f64::consts::PI,
+            epsilon = 1e-10
+        );
+    }
+}
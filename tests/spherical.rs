@@ -0,0 +1,91 @@
+use approx::assert_abs_diff_eq;
+use lie::spherical::*;
+use lie::su2;
+use ndarray as nd;
+
+// The spherical components Q_{1,0} = J_z and Q_{1,+-1} = -+J_+-/sqrt(2), written out rather than
+// taken from `q_1_u` so that the recursion below does not check the crate against itself. Before
+// `q_1_u` was normalized it returned -+J_+- without the 1/sqrt(2), and every higher rank with it.
+fn q_1_u_expected(j: f64, u: i32) -> nd::Array2<f64> {
+    match u {
+        0 => su2::s_z(j),
+        1 => -su2::j_p(j) / 2f64.sqrt(),
+        -1 => su2::j_m(j) / 2f64.sqrt(),
+        _ => unreachable!(),
+    }
+}
+
+// The direct recursion, which recomputes every lower rank
+fn q_r_u_recursive(j: f64, r: i32, u: i32) -> nd::Array2<f64> {
+    if r == 1 {
+        q_1_u_expected(j, u)
+    } else {
+        let n = (j * 2. + 1.) as usize;
+
+        let mut mat: nd::Array2<f64> = nd::Array2::zeros((n, n));
+
+        for i in (-1 + u)..=(u + 1) {
+            if i.abs() < r {
+                let q1u = q_1_u_expected(j, -i + u);
+                mat = mat + c_g(r - 1, i, -i + u) * q_r_u_recursive(j, r - 1, i).dot(&q1u);
+            }
+        }
+        mat
+    }
+}
+
+#[test]
+fn test_basis_matches_recursion() {
+    for &j in [0.5, 1., 1.5, 2., 2.5].iter() {
+        let n = (j * 2. + 1.) as i32;
+
+        let mut expected = Vec::new();
+        for r in 1..n {
+            for u in -r..=r {
+                expected.push(q_r_u_recursive(j, r, u));
+                assert_abs_diff_eq!(q_r_u(j, r, u), expected.last().unwrap(), epsilon = 1e-12);
+            }
+        }
+
+        let basis = basis_from_spin(j);
+        assert_eq!(basis.len(), expected.len());
+        for (x, y) in basis.iter().zip(&expected) {
+            assert_abs_diff_eq!(x, y, epsilon = 1e-12);
+        }
+    }
+}
+
+#[test]
+fn test_rank_one() {
+    for &j in [0.5, 1., 2.5].iter() {
+        for u in -1..=1 {
+            assert_abs_diff_eq!(q_1_u(j, u), q_1_u_expected(j, u), epsilon = 1e-12);
+        }
+    }
+}
+
+#[test]
+fn test_higher_spin_basis_spans_su_n() {
+    use lie::verify::verify_lie_algebra;
+
+    // With the unnormalized Q_{1,+-1} the higher ranks were neither traceless nor closed
+    for &j in [1., 1.5, 2.].iter() {
+        let report = verify_lie_algebra(&hermitian_basis_from_spin(j));
+        assert!(report.is_lie_algebra());
+        assert!(report.is_traceless());
+    }
+}
+
+#[test]
+fn test_hermitian_basis_size() {
+    let basis = hermitian_basis_from_spin(10.);
+    assert_eq!(basis.len(), 21 * 21 - 1);
+}
+
+#[test]
+fn test_hermitian_basis_large_spin() {
+    // Reserving 2^n - 1 elements instead of n^2 - 1 aborts or overflows well before j = 20
+    let basis = hermitian_basis_from_spin(20.);
+    assert_eq!(basis.len(), 41 * 41 - 1);
+    assert!(basis.iter().all(|x| x.shape() == [41, 41]));
+}
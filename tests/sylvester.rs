@@ -1,3 +1,4 @@
+use approx::assert_abs_diff_eq;
 use lie::lie_algebra::*;
 use lie::sylvester::*;
 
@@ -8,3 +9,47 @@ fn test_sylvester_structure() {
 
     println!("{}", x.len());
 }
+
+#[test]
+fn test_weyl_basis_labels() {
+    for d in 2..6 {
+        let weyl = WeylBasis::new(d);
+        let basis = gen_sylvester(d);
+        assert_eq!(weyl.dim(), basis.len());
+
+        for (i, t) in basis.iter().enumerate() {
+            assert_abs_diff_eq!(weyl.matrix(i), t, epsilon = 1e-8);
+
+            let (r, s) = weyl.label(i);
+            assert_eq!(weyl.index(r, s), Some(i));
+        }
+        assert_eq!(weyl.index(0, 0), None);
+    }
+}
+
+#[test]
+fn test_weyl_basis_product() {
+    let d = 4;
+    let weyl = WeylBasis::new(d);
+    let basis = gen_sylvester(d);
+
+    for (a, t_a) in basis.iter().enumerate() {
+        for (b, t_b) in basis.iter().enumerate() {
+            let (phase, c) = weyl.product(a, b);
+            let expected = match c {
+                Some(c) => &basis[c] * phase,
+                None => ndarray::Array2::eye(d) * phase,
+            };
+            assert_abs_diff_eq!(t_a.dot(t_b), expected, epsilon = 1e-8);
+        }
+    }
+}
+
+#[test]
+fn test_weyl_structure_constants() {
+    for d in 2..5 {
+        let f = WeylBasis::new(d).structure_constants();
+        let expected = find_structure_constants(&gen_sylvester(d));
+        assert_abs_diff_eq!(f.to_dense(), expected.to_dense(), epsilon = 1e-8);
+    }
+}
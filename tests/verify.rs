@@ -0,0 +1,92 @@
+use lie::gellmann::*;
+use lie::spherical::*;
+use lie::sylvester::*;
+use lie::verify::*;
+use ndarray as nd;
+use num_complex::Complex64;
+
+#[test]
+fn test_gellmann_is_lie_algebra() {
+    let report = verify_lie_algebra(&gen_gellmann(3));
+
+    assert_eq!(report.rank, 8);
+    assert!(report.is_lie_algebra());
+    assert!(report.is_traceless());
+    assert!(report.is_anti_hermitian());
+}
+
+#[test]
+fn test_sylvester_is_not_anti_hermitian() {
+    let report = verify_lie_algebra(&gen_sylvester(3));
+
+    assert!(report.is_lie_algebra());
+    assert!(report.is_traceless());
+    assert!(!report.is_anti_hermitian());
+}
+
+#[test]
+fn test_non_closed_basis() {
+    // Dropping an element of su(2) leaves two elements whose commutator is outside their span
+    let mut basis = gen_gellmann(2);
+    basis.pop();
+    let report = verify_lie_algebra(&basis);
+
+    assert!(report.is_independent());
+    assert!(!report.is_closed());
+
+    let worst = report.worst_closure().unwrap();
+    assert_eq!((worst.a, worst.b), (0, 1));
+    assert!(worst.residual > 1.);
+}
+
+#[test]
+#[should_panic(expected = "The basis is not closed under the commutator: [T_0, T_1]")]
+fn test_non_closed_structure_constants() {
+    let mut basis = gen_gellmann(2);
+    basis.pop();
+    lie::lie_algebra::find_structure_constants(&basis);
+}
+
+#[test]
+fn test_empty_basis() {
+    let report = verify_lie_algebra(&[]);
+
+    assert_eq!(report.dim, 0);
+    assert!(report.is_lie_algebra());
+    assert!(report.worst_closure().is_none());
+}
+
+#[test]
+fn test_worst_closure_nan() {
+    let mut basis = gen_gellmann(2);
+    basis.pop();
+    let mut report = verify_lie_algebra(&basis);
+    report.closure[0].residual = f64::NAN;
+
+    // NaN sorts above every number, so it is reported instead of panicking
+    assert!(report.worst_closure().unwrap().residual.is_nan());
+}
+
+#[test]
+fn test_dependent_basis() {
+    let mut basis = gen_gellmann(2);
+    basis.push(&basis[0] * Complex64::new(2., 0.));
+    let report = verify_lie_algebra(&basis);
+
+    assert_eq!(report.dim, 4);
+    assert_eq!(report.rank, 3);
+    assert!(!report.is_lie_algebra());
+    assert!(report.is_closed());
+}
+
+#[test]
+fn test_spherical_basis_report() {
+    // The spin 1/2 spherical basis is Hermitian rather than anti-Hermitian
+    let report = verify_lie_algebra(&hermitian_basis_from_spin(0.5));
+
+    assert!(report.is_lie_algebra());
+    assert!(!report.is_anti_hermitian());
+
+    let identity: nd::Array2<Complex64> = nd::Array2::eye(2);
+    assert!(!verify_lie_algebra(&[identity]).is_traceless());
+}
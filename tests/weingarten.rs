@@ -0,0 +1,209 @@
+use approx::assert_abs_diff_eq;
+use itertools::Itertools;
+use lie::gellmann::*;
+use lie::random::*;
+use lie::weingarten::*;
+use ndarray as nd;
+use ndarray_linalg::*;
+use num_bigint::BigInt;
+use num_complex::Complex64;
+use num_rational::BigRational;
+use num_traits::ToPrimitive;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn ratio(p: i64, q: i64) -> BigRational {
+    BigRational::new(BigInt::from(p), BigInt::from(q))
+}
+
+#[test]
+fn test_characters() {
+    // The character table of S_3
+    assert_eq!(character(&[3], &[2, 1]), 1);
+    assert_eq!(character(&[2, 1], &[1, 1, 1]), 2);
+    assert_eq!(character(&[2, 1], &[2, 1]), 0);
+    assert_eq!(character(&[2, 1], &[3]), -1);
+    assert_eq!(character(&[1, 1, 1], &[2, 1]), -1);
+
+    // The squares of the dimensions sum to k!
+    let sum: i64 = partitions(5)
+        .iter()
+        .map(|l| character(l, &[1; 5]).pow(2))
+        .sum();
+    assert_eq!(sum, 120);
+    assert_eq!(partitions(6).len(), 11);
+    assert_eq!(cycle_type(&[1, 2, 0, 4, 3, 5]), vec![3, 2, 1]);
+}
+
+#[test]
+fn test_weingarten_unitary() {
+    for d in 2..6 {
+        let d2 = d * d;
+        assert_eq!(weingarten_unitary(&[0, 1], d as usize), ratio(1, d2 - 1));
+        assert_eq!(
+            weingarten_unitary(&[1, 0], d as usize),
+            ratio(-1, d * (d2 - 1))
+        );
+    }
+    for d in 3..6 {
+        let d2 = d * d;
+        assert_eq!(
+            weingarten_unitary(&[0, 1, 2], d as usize),
+            ratio(d2 - 2, d * (d2 - 1) * (d2 - 4))
+        );
+    }
+
+    // The inverse of the Gram matrix d^{#cycles(sigma^-1 tau)} when d >= k
+    use itertools::Itertools;
+    let (k, d) = (3, 4);
+    let perms: Vec<Vec<usize>> = (0..k).permutations(k).collect();
+    let inverse = |s: &[usize]| {
+        let mut res = vec![0; k];
+        for (a, x) in s.iter().enumerate() {
+            res[*x] = a;
+        }
+        res
+    };
+    let relative = |s: &[usize], t: &[usize]| {
+        let s_inv = inverse(s);
+        t.iter().map(|x| s_inv[*x]).collect::<Vec<_>>()
+    };
+    let gram = nd::Array2::from_shape_fn((perms.len(), perms.len()), |(a, b)| {
+        (d as f64).powi(cycle_type(&relative(&perms[a], &perms[b])).len() as i32)
+    });
+    let wg = gram.inv().unwrap();
+    for a in 0..perms.len() {
+        for b in 0..perms.len() {
+            let exact = weingarten_unitary(&relative(&perms[a], &perms[b]), d);
+            assert_abs_diff_eq!(wg[[a, b]], exact.to_f64().unwrap(), epsilon = 1e-12);
+        }
+    }
+}
+
+#[test]
+fn test_unitary_moments() {
+    // E|U_11|^{2k} = k! (d - 1)! / (d + k - 1)!, also for d < k
+    assert_eq!(
+        unitary_moment(&[0; 3], &[0; 3], &[0; 3], &[0; 3], 2),
+        ratio(1, 4)
+    );
+    assert_eq!(
+        unitary_moment(&[0; 2], &[0; 2], &[0; 2], &[0; 2], 1),
+        ratio(1, 1)
+    );
+    assert_eq!(
+        unitary_moment(&[0; 2], &[0; 2], &[0; 2], &[0; 2], 3),
+        ratio(1, 6)
+    );
+
+    let d = 3;
+    let m1 = unitary_moment(&[0, 1], &[0, 1], &[0, 1], &[0, 1], d);
+    let m2 = unitary_moment(&[0, 1], &[0, 1], &[0, 1], &[1, 0], d);
+    assert_eq!(m1, ratio(1, 8));
+    assert_eq!(m2, ratio(-1, 24));
+    assert_eq!(unitary_moment(&[0], &[0], &[], &[], d), ratio(0, 1));
+
+    // Monte Carlo over U(3)
+    let mut rng = StdRng::seed_from_u64(1);
+    let samples = 20000;
+    let (mut s1, mut s2) = (0., Complex64::new(0., 0.));
+    for _ in 0..samples {
+        let u = haar_unitary(d, &mut rng);
+        s1 += u[[0, 0]].norm_sqr() * u[[1, 1]].norm_sqr();
+        s2 += u[[0, 0]] * u[[1, 1]] * (u[[0, 1]] * u[[1, 0]]).conj();
+    }
+    assert_abs_diff_eq!(s1 / samples as f64, m1.to_f64().unwrap(), epsilon = 0.01);
+    assert_abs_diff_eq!(s2.re / samples as f64, m2.to_f64().unwrap(), epsilon = 0.01);
+    assert_abs_diff_eq!(s2.im / samples as f64, 0., epsilon = 0.01);
+}
+
+#[test]
+fn test_orthogonal() {
+    assert_eq!(pair_partitions(4).len(), 3);
+    assert_eq!(pair_partitions(6).len(), 15);
+
+    for d in 2..6i64 {
+        let wg = weingarten_orthogonal(2, d as usize);
+        let denominator = d * (d - 1) * (d + 2);
+        assert_eq!(wg[0][0], ratio(d + 1, denominator));
+        assert_eq!(wg[0][1], ratio(-1, denominator));
+
+        let du = d as usize;
+        assert_eq!(orthogonal_moment(&[0, 0], &[0, 0], du), ratio(1, d));
+        assert_eq!(
+            orthogonal_moment(&[0; 4], &[0; 4], du),
+            ratio(3, d * (d + 2))
+        );
+        assert_eq!(
+            orthogonal_moment(&[0, 0, 0, 0], &[0, 0, 1, 1], du),
+            ratio(1, d * (d + 2))
+        );
+        assert_eq!(orthogonal_moment(&[0; 3], &[0; 3], du), ratio(0, 1));
+
+        // Every moment of degree 4 from a single Weingarten matrix
+        for i in (0..4).map(|_| 0..2).multi_cartesian_product() {
+            let j = [0, 1, 1, 0];
+            assert_eq!(
+                orthogonal_moment_from_weingarten(&i, &j, &wg),
+                orthogonal_moment(&i, &j, du)
+            );
+        }
+    }
+
+    // O(1) = {1, -1} has a singular Gram matrix
+    assert_eq!(orthogonal_moment(&[0; 4], &[0; 4], 1), ratio(1, 1));
+    assert_eq!(orthogonal_moment(&[0; 6], &[0; 6], 1), ratio(1, 1));
+    // E O_11^6 = 15 / (d (d + 2) (d + 4)), with d < 3 for O(2)
+    assert_eq!(orthogonal_moment(&[0; 6], &[0; 6], 2), ratio(15, 48));
+
+    // Monte Carlo over O(3)
+    let d = 3;
+    let i = [0, 1, 0, 1];
+    let j = [0, 1, 1, 0];
+    let exact = orthogonal_moment(&i, &j, d).to_f64().unwrap();
+    let mut rng = StdRng::seed_from_u64(2);
+    let samples = 20000;
+    let mc = (0..samples)
+        .map(|_| {
+            let o = haar_orthogonal(d, &mut rng);
+            (0..4).map(|a| o[[i[a], j[a]]]).product::<f64>()
+        })
+        .sum::<f64>()
+        / samples as f64;
+    assert_abs_diff_eq!(mc, exact, epsilon = 0.005);
+}
+
+#[test]
+fn test_twirl() {
+    let d = 3;
+    let kron = |a: &nd::Array2<Complex64>, b: &nd::Array2<Complex64>| {
+        let (n, m) = (a.shape()[0], b.shape()[0]);
+        nd::Array2::from_shape_fn((n * m, n * m), |(i, j)| {
+            a[[i / m, j / m]] * b[[i % m, j % m]]
+        })
+    };
+
+    // A single copy is sent to Tr(X) I / d
+    let x = nd::Array2::from_shape_fn((d, d), |(i, j)| Complex64::new(i as f64, j as f64));
+    let expected = nd::Array2::eye(d) * (x.diag().sum() / d as f64);
+    assert_abs_diff_eq!(twirl(&x, d, 1), expected, epsilon = 1e-12);
+
+    // Two copies of Gell-Mann matrices against Monte Carlo
+    let basis = gen_gellmann(d);
+    let x = kron(&basis[0], &basis[3]) + kron(&basis[2], &basis[2]);
+    let exact = twirl(&x, d, 2);
+    let mut rng = StdRng::seed_from_u64(4);
+    let samples = 4000;
+    let mut mc = nd::Array2::zeros((d * d, d * d));
+    for _ in 0..samples {
+        let u = haar_unitary(d, &mut rng);
+        let uu = kron(&u, &u);
+        mc += &uu.dot(&x).dot(&uu.t().mapv(|x| x.conj()));
+    }
+    mc /= Complex64::new(samples as f64, 0.);
+    assert_abs_diff_eq!(mc, exact, epsilon = 0.05);
+
+    // The twirl only depends on the overlaps with the identity and the swap
+    let twice = twirl(&exact, d, 2);
+    assert_abs_diff_eq!(twice, exact, epsilon = 1e-12);
+}
@@ -0,0 +1,102 @@
+use approx::assert_abs_diff_eq;
+use lie::euler::*;
+use lie::exponential::*;
+use lie::su2::*;
+use lie::wigner::*;
+use ndarray as nd;
+use num_complex::Complex64;
+
+fn scaled(x: &nd::Array2<Complex64>, c: Complex64) -> nd::Array2<Complex64> {
+    x * c
+}
+
+#[test]
+fn test_against_expm() {
+    let (alpha, beta, gamma) = (0.9, 2.3, -1.7);
+    for &j in [0., 0.5, 1., 1.5, 2., 3.5, 6.].iter() {
+        let [s_z, _, i_s_y] = gen_su2(j);
+        let rz = |theta: f64| expm(&scaled(&s_z, Complex64::new(0., -theta)));
+
+        // exp(-i beta J_y) = exp(-beta (i J_y))
+        let ry = expm(&scaled(&i_s_y, Complex64::new(-beta, 0.)));
+        assert_abs_diff_eq!(
+            wigner_small_d(j, beta).mapv(|x| Complex64::new(x, 0.)),
+            ry,
+            epsilon = 1e-10
+        );
+        assert_abs_diff_eq!(
+            wigner_d(j, alpha, beta, gamma),
+            rz(alpha).dot(&ry).dot(&rz(gamma)),
+            epsilon = 1e-10
+        );
+    }
+
+    assert_abs_diff_eq!(
+        wigner_d(0.5, alpha, beta, gamma),
+        su2_from_euler(alpha, beta, gamma, EulerConvention::Zyz),
+        epsilon = 1e-12
+    );
+}
+
+#[test]
+fn test_closed_forms() {
+    let beta = 1.1f64;
+    let (c, s) = ((beta / 2.).cos(), (beta / 2.).sin());
+
+    let d = wigner_small_d(0.5, beta);
+    assert_abs_diff_eq!(d, nd::arr2(&[[c, -s], [s, c]]), epsilon = 1e-14);
+
+    let d = wigner_small_d(1., beta);
+    let sq = std::f64::consts::SQRT_2;
+    let expected = nd::arr2(&[
+        [c * c, -sq * s * c, s * s],
+        [sq * s * c, beta.cos(), -sq * s * c],
+        [s * s, sq * s * c, c * c],
+    ]);
+    assert_abs_diff_eq!(d, expected, epsilon = 1e-14);
+}
+
+#[test]
+fn test_large_j() {
+    let (beta_1, beta_2) = (0.7f64, 1.9f64);
+    for &j in [60., 80.5].iter() {
+        let n = (2. * j + 1.) as usize;
+        let d_1 = wigner_small_d(j, beta_1);
+        let d_2 = wigner_small_d(j, beta_2);
+
+        // Corner elements d_{jj} = cos^{2j} and d_{j,-j} = (-sin)^{2j} of beta / 2, at angles
+        // where they are of order 1 rather than below the tolerance
+        let beta = 0.05;
+        assert_abs_diff_eq!(
+            wigner_small_d(j, beta)[[0, 0]],
+            (beta / 2.).cos().powf(2. * j),
+            epsilon = 1e-12
+        );
+        let beta = std::f64::consts::PI - 0.05;
+        assert_abs_diff_eq!(
+            wigner_small_d(j, beta)[[0, n - 1]],
+            (-(beta / 2.).sin()).powi((2. * j) as i32),
+            epsilon = 1e-12
+        );
+
+        // The whole first row, d_{j,j-k} = sqrt(C(2j, k)) cos^{2j-k} (-sin)^k of beta / 2
+        let (cos, sin) = ((beta_2 / 2.).cos(), (beta_2 / 2.).sin());
+        let mut log_binomial = 0.;
+        for k in 0..n {
+            if k > 0 {
+                log_binomial += ((n - k) as f64).ln() - (k as f64).ln();
+            }
+            let magnitude =
+                (0.5 * log_binomial + (n - 1 - k) as f64 * cos.ln() + k as f64 * sin.ln()).exp();
+            let sign = if k % 2 == 0 { 1. } else { -1. };
+            assert_abs_diff_eq!(d_2[[0, k]], sign * magnitude, epsilon = 1e-10);
+        }
+
+        assert_abs_diff_eq!(d_1.t().dot(&d_1), nd::Array2::eye(n), epsilon = 1e-10);
+        assert_abs_diff_eq!(
+            d_1.dot(&d_2),
+            wigner_small_d(j, beta_1 + beta_2),
+            epsilon = 1e-10
+        );
+    }
+}